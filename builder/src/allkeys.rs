@@ -0,0 +1,146 @@
+// разбор allkeys.txt (DUCET), формат строк:
+//
+// 0041 ; [.2075.0020.0008] # LATIN CAPITAL LETTER A
+// 004C 00B7 ; [.21E5.0020.0008][.0000.011C.0002] # LATIN CAPITAL LETTER L WITH MIDDLE DOT
+//
+// строки @implicitweights пропускаются - вычисляемые веса коллатор получает сам
+
+use std::collections::HashMap;
+
+use crate::BuildError;
+
+/// веса из allkeys
+pub struct AllKeys
+{
+    /// версия Unicode (@version), 0 - не указана
+    pub version: [u8; 3],
+    /// веса отдельных кодпоинтов
+    pub singles: HashMap<u32, Vec<u32>>,
+    /// последовательности (сокращения, many-to-many) в порядке их записи в файле
+    pub sequences: Vec<(Vec<u32>, Vec<u32>)>,
+}
+
+impl AllKeys
+{
+    /// разобрать содержимое allkeys
+    pub fn parse(source: &str) -> Result<Self, BuildError>
+    {
+        let mut allkeys = Self {
+            version: [0; 3],
+            singles: HashMap::new(),
+            sequences: vec![],
+        };
+
+        for (line, text) in (1 ..).zip(source.lines()) {
+            let text = text.split('#').next().unwrap_or_default().trim();
+
+            if text.is_empty() {
+                continue;
+            }
+
+            if let Some(version) = text.strip_prefix("@version") {
+                allkeys.version = parse_version(version).ok_or(BuildError::InvalidAllKeys(line))?;
+                continue;
+            }
+
+            if text.starts_with('@') {
+                continue;
+            }
+
+            let (codes, weights) = text
+                .split_once(';')
+                .ok_or(BuildError::InvalidAllKeys(line))?;
+
+            let codes = parse_codes(codes).ok_or(BuildError::InvalidAllKeys(line))?;
+            let weights = parse_weights(weights, line)?;
+
+            match codes.as_slice() {
+                [code] => {
+                    allkeys.singles.insert(*code, weights);
+                }
+                _ => allkeys.sequences.push((codes, weights)),
+            }
+        }
+
+        Ok(allkeys)
+    }
+}
+
+/// версия вида 15.1.0
+fn parse_version(source: &str) -> Option<[u8; 3]>
+{
+    let mut version = [0; 3];
+    let mut parts = source.trim().split('.');
+
+    for part in version.iter_mut() {
+        *part = parts.next()?.parse().ok()?;
+    }
+
+    match parts.next() {
+        None => Some(version),
+        Some(_) => None,
+    }
+}
+
+/// кодпоинты, разделённые пробелами
+pub fn parse_codes(source: &str) -> Option<Vec<u32>>
+{
+    let codes = source
+        .split_whitespace()
+        .map(|code| {
+            u32::from_str_radix(code, 16)
+                .ok()
+                .filter(|&code| code <= 0x10FFFF)
+        })
+        .collect::<Option<Vec<u32>>>()?;
+
+    match codes.is_empty() {
+        true => None,
+        false => Some(codes),
+    }
+}
+
+/// веса вида [.2075.0020.0008][*0209.0020.0002], в том виде, в котором они записываются в таблицы:
+/// L1 - 16 бит, L2 - 9 бит, L3 - 5 бит, флаг переменного веса
+///
+/// четвёртый уровень (в allkeys до Unicode 9) игнорируется
+fn parse_weights(source: &str, line: usize) -> Result<Vec<u32>, BuildError>
+{
+    let mut weights = vec![];
+    let mut rest = source.trim();
+
+    while !rest.is_empty() {
+        let (element, tail) = rest
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once(']'))
+            .ok_or(BuildError::InvalidAllKeys(line))?;
+
+        rest = tail.trim_start();
+
+        let is_variable = match element.chars().next() {
+            Some('*') => true,
+            Some('.') => false,
+            _ => return Err(BuildError::InvalidAllKeys(line)),
+        };
+
+        let levels = element[1 ..]
+            .split('.')
+            .map(|level| u32::from_str_radix(level, 16).ok())
+            .collect::<Option<Vec<u32>>>()
+            .filter(|levels| (3 ..= 4).contains(&levels.len()))
+            .ok_or(BuildError::InvalidAllKeys(line))?;
+
+        if levels[0] > 0xFFFF || levels[1] > 0x1FF || levels[2] > 0x1F {
+            return Err(BuildError::WeightsOverflow(line));
+        }
+
+        let value = levels[0] | (levels[1] << 16) | (levels[2] << 25);
+
+        weights.push(value | (is_variable as u32) << 30);
+    }
+
+    match weights.is_empty() {
+        true => Err(BuildError::InvalidAllKeys(line)),
+        false => Ok(weights),
+    }
+}
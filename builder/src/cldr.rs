@@ -0,0 +1,62 @@
+// корневая сортировка CLDR (und) из каталога common/uca
+//
+// CLDR поставляет корневую сортировку в двух видах: FractionalUCA.txt - дробные веса для ICU, и
+// allkeys_CLDR.txt - та же сортировка в формате allkeys (DUCET с изменениями CLDR, например, в
+// порядке символов и валют). таблицы собираются только из allkeys_CLDR.txt: перевод дробных
+// весов в формат таблиц не реализован, каталог с одним FractionalUCA.txt - UnsupportedSource
+//
+// у корневой сортировки (collation/root.xml, тип standard) нет правил поверх этих весов, поэтому
+// других файлов CLDR, кроме common/dtd/ldml.dtd с версией CLDR, не требуется
+
+use std::fs;
+use std::path::Path;
+
+use crate::{build, BuildError, Tables};
+
+/// корневая сортировка CLDR в формате allkeys
+pub const ALLKEYS_CLDR: &str = "allkeys_CLDR.txt";
+/// корневая сортировка CLDR с дробными весами
+pub const FRACTIONAL_UCA: &str = "FractionalUCA.txt";
+/// DTD LDML относительно каталога common/uca - в нём записана версия CLDR (cldrVersion)
+pub const LDML_DTD: &str = "../dtd/ldml.dtd";
+
+/// собрать таблицы корневой сортировки CLDR: каталог common/uca и содержимое UnicodeData.txt
+///
+/// версия CLDR берётся из common/dtd/ldml.dtd (см. LDML_DTD)
+pub fn build_cldr_root(uca_dir: &Path, unicode_data: &str) -> Result<Tables, BuildError>
+{
+    let path = uca_dir.join(ALLKEYS_CLDR);
+
+    // дробные веса в формат таблиц не переводятся - в том же каталоге должен быть allkeys_CLDR.txt
+    if !path.exists() && uca_dir.join(FRACTIONAL_UCA).exists() {
+        return Err(BuildError::UnsupportedSource(uca_dir.join(FRACTIONAL_UCA)));
+    }
+
+    let allkeys = fs::read_to_string(&path).map_err(|_| BuildError::ReadFailed(path))?;
+
+    let mut tables = build(&allkeys, unicode_data)?;
+
+    let path = uca_dir.join(LDML_DTD);
+    let dtd = fs::read_to_string(&path).map_err(|_| BuildError::ReadFailed(path.clone()))?;
+
+    tables.version.cldr = cldr_version(&dtd).ok_or(BuildError::InvalidVersion(path))?;
+
+    Ok(tables)
+}
+
+/// версия CLDR из ldml.dtd - фиксированное значение атрибута cldrVersion: "44" или "44.1"
+fn cldr_version(dtd: &str) -> Option<[u8; 2]>
+{
+    let line = dtd
+        .lines()
+        .find(|line| line.contains("cldrVersion") && line.contains("#FIXED"))?;
+
+    let mut parts = line.split('"').nth(1)?.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |minor| minor.parse().ok())?;
+
+    match parts.next() {
+        None => Some([major, minor]),
+        Some(_) => None,
+    }
+}
@@ -0,0 +1,123 @@
+// сборка таблиц весов (WeightsData) из allkeys.txt (DUCET) или allkeys_CLDR.txt (корневая
+// сортировка CLDR, см. build_cldr_root) и UnicodeData.txt
+//
+// результат - исходный код в формате data/cldr_und.txt (см. Tables::to_source) или двоичный
+// контейнер (см. Tables::to_bytes). собранные таблицы можно сравнить с эталонными (см. compare)
+//
+// данные NFD для идентичного уровня (data/nfd.txt) собираются из UnicodeData.txt (см. build_nfd)
+
+use core::fmt;
+use std::path::PathBuf;
+
+use unicode_collator::container::DataVersion;
+use unicode_collator::{CollatorData, DataError};
+
+pub use cldr::{build_cldr_root, ALLKEYS_CLDR, FRACTIONAL_UCA, LDML_DTD};
+pub use nfd::build_nfd;
+pub use tables::Tables;
+pub use verify::{compare, Comparison};
+
+mod allkeys;
+mod cldr;
+mod nfd;
+mod sequences;
+mod source;
+mod tables;
+mod unicode;
+mod verify;
+
+/// ошибка сборки таблиц
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildError
+{
+    /// строка allkeys не разобрана
+    InvalidAllKeys(usize),
+    /// веса в строке allkeys не помещаются в формат таблиц (L1 - 16 бит, L2 - 9 бит, L3 - 5 бит)
+    WeightsOverflow(usize),
+    /// строка UnicodeData не разобрана
+    InvalidUnicodeData(usize),
+    /// классов CCC больше, чем помещается в узел бора
+    TooManyCccClasses(usize),
+    /// кодпоинт с весами за пределами диапазонов, которые можно записать в таблицы
+    CodepointOutOfRange(u32),
+    /// таблица (или её элемент) длиннее, чем позволяет адресовать формат
+    TableTooLarge(&'static str),
+    /// собранные таблицы не прошли проверку
+    InvalidData(DataError),
+    /// некорректная строка исходного кода таблиц (см. Tables::from_source)
+    InvalidSource(usize),
+    /// в исходном коде таблиц нет таблицы или её значения не помещаются в тип элементов
+    InvalidTable(&'static str),
+    /// не удалось прочитать файл
+    ReadFailed(PathBuf),
+    /// в файле не найдена версия данных или она некорректна
+    InvalidVersion(PathBuf),
+    /// формат файла не поддерживается (FractionalUCA.txt - нужен allkeys_CLDR.txt)
+    UnsupportedSource(PathBuf),
+}
+
+impl fmt::Display for BuildError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            Self::InvalidAllKeys(line) => write!(f, "некорректная строка allkeys: {}", line),
+            Self::WeightsOverflow(line) => {
+                write!(
+                    f,
+                    "веса не помещаются в формат таблиц, строка allkeys: {}",
+                    line
+                )
+            }
+            Self::InvalidUnicodeData(line) => {
+                write!(f, "некорректная строка UnicodeData: {}", line)
+            }
+            Self::TooManyCccClasses(count) => write!(f, "слишком много классов CCC: {}", count),
+            Self::CodepointOutOfRange(code) => {
+                write!(f, "кодпоинт U+{:04X} невозможно записать в таблицы", code)
+            }
+            Self::TableTooLarge(table) => write!(f, "слишком большая таблица: {}", table),
+            Self::InvalidData(error) => write!(f, "собранные таблицы некорректны: {}", error),
+            Self::InvalidSource(line) => {
+                write!(f, "некорректная строка исходного кода таблиц: {}", line)
+            }
+            Self::InvalidTable(table) => write!(f, "некорректная таблица: {}", table),
+            Self::ReadFailed(path) => write!(f, "не удалось прочитать {}", path.display()),
+            Self::InvalidVersion(path) => {
+                write!(f, "некорректная версия данных в {}", path.display())
+            }
+            Self::UnsupportedSource(path) => {
+                write!(
+                    f,
+                    "формат не поддерживается: {}, нужен {}",
+                    path.display(),
+                    ALLKEYS_CLDR
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// собрать таблицы из содержимого allkeys.txt и UnicodeData.txt
+///
+/// версия Unicode берётся из заголовка allkeys (@version) - в UnicodeData.txt её нет
+pub fn build(allkeys: &str, unicode_data: &str) -> Result<Tables, BuildError>
+{
+    let unicode = unicode::UnicodeData::parse(unicode_data)?;
+    let allkeys = allkeys::AllKeys::parse(allkeys)?;
+    let sequences = sequences::Sequences::new(&unicode, &allkeys);
+
+    let version = DataVersion {
+        unicode: allkeys.version,
+        ..Default::default()
+    };
+
+    let tables = Tables::new(&unicode, &sequences, version)?;
+
+    // та же проверка, что и у таблиц, полученных извне
+    CollatorData::try_from_baked(tables.as_weights_data()).map_err(BuildError::InvalidData)?;
+
+    Ok(tables)
+}
@@ -0,0 +1,127 @@
+// сборка таблиц весов:
+//
+// unicode_collation_builder allkeys <allkeys.txt> <UnicodeData.txt> <результат> [--binary]
+// unicode_collation_builder cldr <каталог common/uca> <UnicodeData.txt> <результат> [--binary]
+// unicode_collation_builder verify <каталог common/uca> <UnicodeData.txt> <cldr_und.txt>
+// unicode_collation_builder nfd <UnicodeData.txt> <результат>
+//
+// allkeys - из allkeys.txt (DUCET), cldr - корневая сортировка CLDR из allkeys_CLDR.txt (версия
+// CLDR - из common/dtd/ldml.dtd). FractionalUCA.txt не поддерживается - в каталоге common/uca
+// должен быть allkeys_CLDR.txt. по умолчанию результат - исходный код для include! (как
+// data/cldr_und.txt), с --binary - двоичный контейнер для Collator::from_bytes
+//
+// verify собирает корневую сортировку CLDR и сравнивает её с таблицами из cldr_und.txt, nfd - данные
+// канонической декомпозиции для идентичного уровня (data/nfd.txt)
+
+use std::path::Path;
+use std::process::ExitCode;
+use std::{env, fs};
+
+use unicode_collation_builder::{build, build_cldr_root, build_nfd, compare, Tables};
+
+const USAGE: &str = "использование:
+  unicode_collation_builder allkeys <allkeys.txt> <UnicodeData.txt> <результат> [--binary]
+  unicode_collation_builder cldr <каталог common/uca> <UnicodeData.txt> <результат> [--binary]
+  unicode_collation_builder verify <каталог common/uca> <UnicodeData.txt> <cldr_und.txt>
+  unicode_collation_builder nfd <UnicodeData.txt> <результат>
+
+cldr и verify читают только allkeys_CLDR.txt, FractionalUCA.txt не поддерживается";
+
+/// сколько отличающихся кодпоинтов выводить
+const MAX_REPORTED_CODEPOINTS: usize = 32;
+
+fn main() -> ExitCode
+{
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["allkeys", allkeys, unicode_data, output, flags @ ..] if is_binary(flags).is_some() => {
+            let tables = read(allkeys).and_then(|allkeys| {
+                build(&allkeys, &read(unicode_data)?).map_err(|error| error.to_string())
+            });
+
+            tables.and_then(|tables| write(&tables, output, is_binary(flags) == Some(true)))
+        }
+        ["cldr", uca_dir, unicode_data, output, flags @ ..] if is_binary(flags).is_some() => {
+            cldr_root(uca_dir, unicode_data)
+                .and_then(|tables| write(&tables, output, is_binary(flags) == Some(true)))
+        }
+        ["verify", uca_dir, unicode_data, expected] => verify(uca_dir, unicode_data, expected),
+        ["nfd", unicode_data, output] => read(unicode_data).and_then(|unicode_data| {
+            let source = build_nfd(&unicode_data).map_err(|error| error.to_string())?;
+
+            fs::write(output, source)
+                .map_err(|error| format!("не удалось записать {}: {}", output, error))
+        }),
+        _ => Err(USAGE.to_owned()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// флаг --binary, None - некорректные аргументы
+fn is_binary(flags: &[&str]) -> Option<bool>
+{
+    match flags {
+        [] => Some(false),
+        ["--binary"] => Some(true),
+        _ => None,
+    }
+}
+
+/// прочитать входной файл
+fn read(path: &str) -> Result<String, String>
+{
+    fs::read_to_string(path).map_err(|error| format!("не удалось прочитать {}: {}", path, error))
+}
+
+/// собрать корневую сортировку CLDR
+fn cldr_root(uca_dir: &str, unicode_data: &str) -> Result<Tables, String>
+{
+    build_cldr_root(Path::new(uca_dir), &read(unicode_data)?).map_err(|error| error.to_string())
+}
+
+/// записать результат
+fn write(tables: &Tables, output: &str, is_binary: bool) -> Result<(), String>
+{
+    let result = match is_binary {
+        true => tables.to_bytes(),
+        false => tables.to_source().into_bytes(),
+    };
+
+    fs::write(output, result).map_err(|error| format!("не удалось записать {}: {}", output, error))
+}
+
+/// собрать корневую сортировку CLDR и сравнить её с эталонными таблицами
+fn verify(uca_dir: &str, unicode_data: &str, expected: &str) -> Result<(), String>
+{
+    let tables = cldr_root(uca_dir, unicode_data)?;
+    let expected = Tables::from_source(&read(expected)?).map_err(|error| error.to_string())?;
+
+    let comparison = compare(&tables, &expected);
+
+    if comparison.is_identical() {
+        println!("таблицы совпадают");
+        return Ok(());
+    }
+
+    for (table, position) in &comparison.tables {
+        println!("{}: отличие в позиции {}", table, position);
+    }
+
+    for code in comparison.codepoints.iter().take(MAX_REPORTED_CODEPOINTS) {
+        println!("U+{:04X}: веса отличаются", code);
+    }
+
+    Err(format!(
+        "таблицы отличаются, кодпоинтов с другими весами: {}",
+        comparison.codepoints.len()
+    ))
+}
@@ -0,0 +1,47 @@
+// данные канонической декомпозиции для идентичного уровня (NormalizationData, см. data/nfd.txt)
+// из UnicodeData.txt - в том же формате исходного кода, что и таблицы весов
+
+use crate::source::write_table;
+use crate::unicode::UnicodeData;
+use crate::BuildError;
+
+/// данные NFD в виде исходного кода (NormalizationData { .. })
+pub fn build_nfd(unicode_data: &str) -> Result<String, BuildError>
+{
+    let unicode = UnicodeData::parse(unicode_data)?;
+
+    let ccc: Vec<u32> = unicode
+        .nonstarters()
+        .into_iter()
+        .map(|code| (code << 8) | unicode.ccc(code) as u32)
+        .collect();
+
+    let codes = unicode.decomposed_codepoints();
+
+    let mut offsets = Vec::with_capacity(codes.len() + 1);
+    let mut decompositions = vec![];
+
+    for &code in &codes {
+        offsets.push(decompositions.len());
+        decompositions.extend(unicode.nfd(&[code]));
+    }
+
+    offsets.push(decompositions.len());
+
+    let offsets = offsets
+        .into_iter()
+        .map(u16::try_from)
+        .collect::<Result<Vec<u16>, _>>()
+        .map_err(|_| BuildError::TableTooLarge("decompositions"))?;
+
+    let mut source = String::from("NormalizationData {\n");
+
+    write_table(&mut source, "ccc", &ccc);
+    write_table(&mut source, "codes", &codes);
+    write_table(&mut source, "offsets", &offsets);
+    write_table(&mut source, "decompositions", &decompositions);
+
+    source.push_str("}\n");
+
+    Ok(source)
+}
@@ -0,0 +1,268 @@
+// последовательности (сокращения и many-to-many) и форма бора, в который они записываются
+//
+// узел бора - последовательность кодпоинтов в NFD (путь от корня). кроме самих последовательностей
+// из allkeys, в бор попадают:
+//
+// - промежуточные узлы: префиксы последовательностей, их веса вычисляются по UCA
+// - продолжения промежуточных узлов: если промежуточный узел заканчивается началом другой
+//   последовательности (0FB2 0F71 + 0F72 -> 0FB2 + 0F71 0F72), иначе, не найдя потомка, коллатор
+//   запишет веса узла и потеряет эту последовательность
+// - кодпоинты с декомпозицией, которая продолжает узел (0FB2 + 0F73 = 0FB2 0F71 0F72) - чтобы
+//   строки в форме FCD не требовали декомпозиции
+
+use std::collections::{HashMap, HashSet};
+
+use unicode_collator::implicit_weights;
+
+use crate::allkeys::AllKeys;
+use crate::unicode::UnicodeData;
+
+/// потомок узла бора
+pub struct Child
+{
+    /// кодпоинт
+    pub code: u32,
+    /// путь к узлу (в NFD), определяет веса
+    pub path: Vec<u32>,
+    /// у узла есть потомки
+    pub is_branch: bool,
+}
+
+/// последовательности кодпоинтов и бор
+pub struct Sequences<'a>
+{
+    unicode: &'a UnicodeData,
+    /// веса отдельных кодпоинтов
+    singles: &'a HashMap<u32, Vec<u32>>,
+    /// веса последовательностей, ключи - в NFD
+    contractions: HashMap<Vec<u32>, Vec<u32>>,
+    /// потомки узлов, отсортированные по CCC и кодпоинту: сначала стартеры, затем нестартеры
+    children: HashMap<Vec<u32>, Vec<Child>>,
+}
+
+impl<'a> Sequences<'a>
+{
+    /// последовательности из allkeys
+    pub fn new(unicode: &'a UnicodeData, allkeys: &'a AllKeys) -> Self
+    {
+        let mut contractions = HashMap::new();
+
+        // последовательности, записанные в виде, отличном от NFD, могут совпадать с уже
+        // записанными - тогда оставляем первую
+        for (codes, weights) in &allkeys.sequences {
+            contractions
+                .entry(unicode.nfd(codes))
+                .or_insert_with(|| weights.clone());
+        }
+
+        let mut sequences = Self {
+            unicode,
+            singles: &allkeys.singles,
+            contractions,
+            children: HashMap::new(),
+        };
+
+        let nodes = sequences.nodes();
+
+        sequences.fill_children(&nodes);
+        sequences
+    }
+
+    /// веса кодпоинта: из allkeys или вычисляемые
+    pub fn single_weights(&self, code: u32) -> Vec<u32>
+    {
+        match self.singles.get(&code) {
+            Some(weights) => weights.clone(),
+            None => implicit_weights(code).to_vec(),
+        }
+    }
+
+    /// веса кодпоинта, если они есть в allkeys
+    pub fn allkeys_weights(&self, code: u32) -> Option<&[u32]>
+    {
+        self.singles.get(&code).map(Vec::as_slice)
+    }
+
+    /// веса узла бора
+    pub fn weights(&self, path: &[u32]) -> Vec<u32>
+    {
+        match self.contractions.get(path) {
+            Some(weights) => weights.clone(),
+            None => self.collate(path),
+        }
+    }
+
+    /// потомки узла бора
+    pub fn children(&self, path: &[u32]) -> &[Child]
+    {
+        self.children.get(path).map_or(&[], Vec::as_slice)
+    }
+
+    /// есть ли у узла бора потомки
+    pub fn has_children(&self, path: &[u32]) -> bool
+    {
+        self.children.contains_key(path)
+    }
+
+    /// путь в боре, целиком совпадающий с декомпозицией стартера - если кодпоинт с такой
+    /// декомпозицией можно записать как обычный стартер: следующие за ним нестартеры не
+    /// могут оказаться перед нестартерами декомпозиции и изменить найденную последовательность
+    pub fn composed_path(&self, decomposition: &[u32]) -> Option<Vec<u32>>
+    {
+        let mut path = vec![decomposition[0]];
+
+        for &code in &decomposition[1 ..] {
+            let children = self.children(&path);
+            let ccc = self.unicode.ccc(code);
+
+            let is_child = children.iter().any(|child| child.code == code);
+            let is_blocked = ccc != 0
+                && children.iter().any(|child| {
+                    let child_ccc = self.unicode.ccc(child.code);
+                    child_ccc != 0 && child_ccc < ccc
+                });
+
+            if !is_child || is_blocked {
+                return None;
+            }
+
+            path.push(code);
+        }
+
+        Some(path)
+    }
+
+    /// веса последовательности кодпоинтов в NFD по UCA: поиск самой длинной последовательности,
+    /// в т.ч. с пропуском нестартеров (S2.1)
+    pub fn collate(&self, codes: &[u32]) -> Vec<u32>
+    {
+        let mut codes = codes.to_vec();
+        let mut result = vec![];
+        let mut start = 0;
+
+        while start < codes.len() {
+            let mut end = codes.len();
+
+            while end > start + 1 && !self.contractions.contains_key(&codes[start .. end]) {
+                end -= 1;
+            }
+
+            let mut sequence = codes[start .. end].to_vec();
+            let mut position = end;
+            let mut last_ccc = 0;
+
+            // следующие за последовательностью нестартеры, не заблокированные пропущенными
+            while position < codes.len() {
+                let ccc = self.unicode.ccc(codes[position]);
+
+                if ccc == 0 {
+                    break;
+                }
+
+                sequence.push(codes[position]);
+
+                if ccc > last_ccc && self.contractions.contains_key(&sequence) {
+                    codes.remove(position);
+                    continue;
+                }
+
+                sequence.pop();
+                last_ccc = last_ccc.max(ccc);
+                position += 1;
+            }
+
+            match sequence.len() {
+                1 => result.extend(self.single_weights(sequence[0])),
+                _ => result.extend_from_slice(&self.contractions[&sequence]),
+            }
+
+            codes.splice(start .. end, sequence.iter().copied());
+            start += sequence.len();
+        }
+
+        result
+    }
+
+    /// узлы бора (кроме корней): последовательности, их префиксы и продолжения промежуточных узлов
+    fn nodes(&self) -> HashSet<Vec<u32>>
+    {
+        let mut nodes = HashSet::new();
+
+        for codes in self.contractions.keys() {
+            for len in 2 ..= codes.len() {
+                nodes.insert(codes[.. len].to_vec());
+            }
+        }
+
+        loop {
+            let mut additions = vec![];
+
+            for path in nodes
+                .iter()
+                .filter(|path| !self.contractions.contains_key(*path))
+            {
+                for start in 1 .. path.len() {
+                    let tail = &path[start ..];
+
+                    for codes in self.contractions.keys() {
+                        if codes.len() > tail.len() && codes.starts_with(tail) {
+                            let continuation = [&path[.. start], codes].concat();
+
+                            for len in path.len() + 1 ..= continuation.len() {
+                                if !nodes.contains(&continuation[.. len]) {
+                                    additions.push(continuation[.. len].to_vec());
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if additions.is_empty() {
+                return nodes;
+            }
+
+            nodes.extend(additions);
+        }
+    }
+
+    /// потомки узлов бора
+    fn fill_children(&mut self, nodes: &HashSet<Vec<u32>>)
+    {
+        let parents: HashSet<&[u32]> = nodes.iter().map(|path| &path[.. path.len() - 1]).collect();
+
+        for path in nodes {
+            let (parent, code) = path.split_at(path.len() - 1);
+
+            self.children
+                .entry(parent.to_vec())
+                .or_default()
+                .push(Child {
+                    code: code[0],
+                    path: path.clone(),
+                    is_branch: parents.contains(path.as_slice()),
+                });
+        }
+
+        // кодпоинты, декомпозиция которых продолжает узел
+        for code in self.unicode.decomposed_codepoints() {
+            let decomposition = self.unicode.nfd(&[code]);
+
+            for (parent, children) in self.children.iter_mut() {
+                let path = [parent.as_slice(), &decomposition].concat();
+
+                if nodes.contains(&path) && !children.iter().any(|child| child.code == code) {
+                    children.push(Child {
+                        code,
+                        path,
+                        is_branch: false,
+                    });
+                }
+            }
+        }
+
+        for children in self.children.values_mut() {
+            children.sort_by_key(|child| (self.unicode.ccc(child.code), child.code));
+        }
+    }
+}
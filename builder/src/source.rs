@@ -0,0 +1,150 @@
+// таблицы в виде исходного кода Rust - в том формате, в котором они подключаются через include!
+// (см. data/cldr_und.txt)
+
+use core::fmt::UpperHex;
+use std::collections::HashMap;
+
+use unicode_collator::container::DataVersion;
+use unicode_collator::CollatorData;
+
+use crate::tables::Tables;
+use crate::BuildError;
+
+/// максимальная длина строки
+const MAX_LINE_WIDTH: usize = 124;
+/// отступ значений таблиц
+const INDENT: &str = "    ";
+
+impl Tables
+{
+    /// таблицы в виде исходного кода (WeightsData { .. })
+    pub fn to_source(&self) -> String
+    {
+        let mut source = String::from("WeightsData {\n");
+
+        write_table(&mut source, "index", &self.index);
+        write_table(&mut source, "scalars32", &self.scalars32);
+        write_table(&mut source, "scalars64", &self.scalars64);
+        write_table(&mut source, "expansions", &self.expansions);
+        write_table(&mut source, "tries", &self.tries);
+
+        source.push_str(&format!(
+            "  continuous_block_end: {:#06X},\n}}\n",
+            self.continuous_block_end
+        ));
+
+        source
+    }
+
+    /// таблицы из исходного кода в том же формате (например, data/cldr_und.txt), с проверкой
+    pub fn from_source(source: &str) -> Result<Self, BuildError>
+    {
+        let mut tables: HashMap<&str, Vec<u64>> = HashMap::new();
+        let mut current: Option<(&str, Vec<u64>)> = None;
+        let mut continuous_block_end = None;
+
+        for (line, text) in (1 ..).zip(source.lines()) {
+            let text = text.trim();
+
+            if let Some(name) = text.strip_suffix(": &[") {
+                current = Some((name, vec![]));
+                continue;
+            }
+
+            if text == "]," {
+                let (name, values) = current.take().ok_or(BuildError::InvalidSource(line))?;
+
+                tables.insert(name, values);
+                continue;
+            }
+
+            match current.as_mut() {
+                Some((_, values)) => {
+                    for value in text
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|value| !value.is_empty())
+                    {
+                        values.push(parse_value(value).ok_or(BuildError::InvalidSource(line))?);
+                    }
+                }
+                None => {
+                    if let Some(value) = text.strip_prefix("continuous_block_end:") {
+                        let value = parse_value(value.trim().trim_end_matches(','))
+                            .and_then(|value| u32::try_from(value).ok())
+                            .ok_or(BuildError::InvalidSource(line))?;
+
+                        continuous_block_end = Some(value);
+                    }
+                }
+            }
+        }
+
+        let tables = Self {
+            index: take_table(&mut tables, "index")?,
+            scalars64: take_table(&mut tables, "scalars64")?,
+            scalars32: take_table(&mut tables, "scalars32")?,
+            expansions: take_table(&mut tables, "expansions")?,
+            tries: take_table(&mut tables, "tries")?,
+            continuous_block_end: continuous_block_end
+                .ok_or(BuildError::InvalidTable("continuous_block_end"))?,
+            version: DataVersion::default(),
+        };
+
+        CollatorData::try_from_baked(tables.as_weights_data()).map_err(BuildError::InvalidData)?;
+
+        Ok(tables)
+    }
+}
+
+/// записать таблицу: значения в hex (0 - без префикса), строки не длиннее MAX_LINE_WIDTH
+pub fn write_table<T: UpperHex + Default + PartialEq>(source: &mut String, name: &str, values: &[T])
+{
+    source.push_str(&format!("  {}: &[\n", name));
+
+    let mut line = String::from(INDENT);
+
+    for value in values {
+        let value = match *value == T::default() {
+            true => String::from("0, "),
+            false => format!("0x{:X}, ", value),
+        };
+
+        if line.len() + value.len() > MAX_LINE_WIDTH {
+            source.push_str(&line);
+            source.push('\n');
+            line = String::from(INDENT);
+        }
+
+        line.push_str(&value);
+    }
+
+    source.push_str(&line);
+    source.push_str("\n  ],\n");
+}
+
+/// значение таблицы: 0 или число в hex с префиксом 0x
+fn parse_value(value: &str) -> Option<u64>
+{
+    match value {
+        "0" => Some(0),
+        _ => u64::from_str_radix(value.strip_prefix("0x")?, 16).ok(),
+    }
+}
+
+/// таблица с указанным именем, значения которой помещаются в тип её элементов
+fn take_table<T: TryFrom<u64>>(
+    tables: &mut HashMap<&str, Vec<u64>>,
+    name: &'static str,
+) -> Result<Vec<T>, BuildError>
+{
+    tables
+        .remove(name)
+        .and_then(|values| {
+            values
+                .into_iter()
+                .map(|value| T::try_from(value).ok())
+                .collect()
+        })
+        .ok_or(BuildError::InvalidTable(name))
+}
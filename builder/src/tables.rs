@@ -0,0 +1,446 @@
+// запись таблиц в формате, который читает коллатор (см. CodepointsIter, TrieIter)
+//
+// запись о кодпоинте (u64): маркер - биты 0..3, далее - веса (u32) или позиция в tries / expansions
+// (биты 4..20) и CCC / длина (биты 20..28). нестартер с одинарными весами - CCC в битах 36..
+//
+// узел бора (u32): количество весов - биты 26.., кодпоинт - биты 8..26, порядковый номер CCC -
+// биты 2..8, бит 1 - последний узел в списке, бит 0 - за узлом следует список потомков. за узлом -
+// его веса
+//
+// индекс: первые 0x600 элементов - блоки по 128 кодпоинтов, начиная с 0x600 - группы по 16
+// индексов блоков данных по 8 кодпоинтов (индекс << 1 | 1, если блок записан в scalars64)
+
+use std::collections::HashMap;
+
+use unicode_collator::container::{self, DataVersion};
+use unicode_collator::{
+    implicit_weights, WeightsData, MARKER_CCC_HANGUL, MARKER_IMPLICIT,
+    MARKER_NONSTARTER_SINGLE_WEIGHTS, MARKER_NONSTARTER_TRIE, MARKER_STARTER_DECOMPOSITION,
+    MARKER_STARTER_EXPANSION, MARKER_STARTER_SINGLE_WEIGHTS, MARKER_STARTER_TRIE,
+};
+
+use crate::sequences::Sequences;
+use crate::unicode::{UnicodeData, MAX_CCC_ORDINAL};
+use crate::BuildError;
+
+/// с U+0000 и до этого кодпоинта включительно блоки индекса идут последовательно
+const CONTINUOUS_BLOCK_END: u32 = 0xFFF;
+/// последний кодпоинт с весами / декомпозицией, следующие за ним - вычисляемые
+const LAST_CODEPOINT: u32 = 0x2FA1D;
+/// кодпоинты с нулевыми весами
+const IGNORABLES: core::ops::Range<u32> = 0xE0000 .. 0xE0200;
+/// первичные индексы для U+E0000.. сдвинуты для уменьшения размера
+const IGNORABLES_SHIFT: u32 = 0x160B;
+/// начало групп индексов блоков данных
+const INDEX_GROUPS_START: usize = 0x600;
+/// суррогаты
+const SURROGATES: core::ops::Range<u32> = 0xD800 .. 0xE000;
+/// слоги хангыль
+const HANGUL_SYLLABLES: core::ops::RangeInclusive<u32> = 0xAC00 ..= 0xD7A3;
+
+/// собранные таблицы весов
+pub struct Tables
+{
+    pub index: Vec<u16>,
+    pub scalars64: Vec<u64>,
+    pub scalars32: Vec<u32>,
+    pub expansions: Vec<u32>,
+    pub tries: Vec<u32>,
+    pub continuous_block_end: u32,
+    /// версия данных для заголовка контейнера
+    pub version: DataVersion,
+}
+
+impl Tables
+{
+    /// собрать таблицы, version - версия данных для заголовка контейнера
+    pub fn new(
+        unicode: &UnicodeData,
+        sequences: &Sequences,
+        version: DataVersion,
+    ) -> Result<Self, BuildError>
+    {
+        let mut encoder = Encoder {
+            unicode,
+            sequences,
+            expansions: vec![],
+            tries: vec![],
+        };
+
+        let mut values = HashMap::new();
+
+        for code in (0 ..= 0x10FFFF).filter(|code| !SURROGATES.contains(code)) {
+            let value = encoder.entry(code)?;
+
+            if value == MARKER_IMPLICIT as u64 {
+                continue;
+            }
+
+            if code > LAST_CODEPOINT && !IGNORABLES.contains(&code) {
+                return Err(BuildError::CodepointOutOfRange(code));
+            }
+
+            values.insert(code, value);
+        }
+
+        let (index, scalars64, scalars32) = build_index(&values)?;
+
+        Ok(Self {
+            index,
+            scalars64,
+            scalars32,
+            expansions: encoder.expansions,
+            tries: encoder.tries,
+            continuous_block_end: CONTINUOUS_BLOCK_END,
+            version,
+        })
+    }
+
+    /// таблицы в том виде, в котором их принимает коллатор
+    pub fn as_weights_data(&self) -> WeightsData<'_>
+    {
+        WeightsData {
+            index: &self.index,
+            scalars64: &self.scalars64,
+            scalars32: &self.scalars32,
+            expansions: &self.expansions,
+            tries: &self.tries,
+            continuous_block_end: self.continuous_block_end,
+        }
+    }
+
+    /// таблицы в виде двоичного контейнера
+    pub fn to_bytes(&self) -> Vec<u8>
+    {
+        container::to_bytes(&self.as_weights_data(), self.version)
+    }
+}
+
+/// запись данных о кодпоинтах, расширений и бора
+struct Encoder<'a>
+{
+    unicode: &'a UnicodeData,
+    sequences: &'a Sequences<'a>,
+    expansions: Vec<u32>,
+    tries: Vec<u32>,
+}
+
+impl<'a> Encoder<'a>
+{
+    /// запись о кодпоинте
+    fn entry(&mut self, code: u32) -> Result<u64, BuildError>
+    {
+        if HANGUL_SYLLABLES.contains(&code) {
+            return Ok(MARKER_STARTER_DECOMPOSITION as u64 | (MARKER_CCC_HANGUL as u64) << 20);
+        }
+
+        let decomposition = self.unicode.nfd(&[code]);
+
+        match decomposition.as_slice() {
+            [single] if *single == code => self.codepoint_entry(code),
+            // синглтон: веса кодпоинта, в который он раскладывается - в т.ч. вычисляемые
+            [single] => {
+                match self.sequences.allkeys_weights(*single).is_some()
+                    || self.sequences.has_children(&[*single])
+                {
+                    true => self.codepoint_entry(*single),
+                    false => self.expansion(&implicit_weights(*single)),
+                }
+            }
+            _ => self.decomposition_entry(code, &decomposition),
+        }
+    }
+
+    /// запись о кодпоинте без декомпозиции
+    fn codepoint_entry(&mut self, code: u32) -> Result<u64, BuildError>
+    {
+        let weights = match self.sequences.allkeys_weights(code) {
+            Some(weights) => weights.to_vec(),
+            None => return Ok(MARKER_IMPLICIT as u64),
+        };
+
+        let has_children = self.sequences.has_children(&[code]);
+
+        if self.unicode.ccc(code) == 0 {
+            return self.starter(code, &weights, has_children.then_some(&[code]));
+        }
+
+        if has_children || weights.len() != 1 {
+            return self.nonstarters(&[code]);
+        }
+
+        Ok(MARKER_NONSTARTER_SINGLE_WEIGHTS as u64
+            | (weights[0] as u64) << 4
+            | (self.unicode.ccc_ordinal(code) as u64) << 36)
+    }
+
+    /// запись о кодпоинте с декомпозицией
+    fn decomposition_entry(&mut self, code: u32, decomposition: &[u32]) -> Result<u64, BuildError>
+    {
+        // декомпозиция начинается с нестартера
+        if self.unicode.ccc(decomposition[0]) != 0 {
+            return self.nonstarters(decomposition);
+        }
+
+        // декомпозиция совпадает с последовательностью - записываем как обычный стартер
+        if let Some(path) = self.sequences.composed_path(decomposition) {
+            let weights = self.sequences.weights(&path);
+            let has_children = self.sequences.has_children(&path);
+
+            return self.starter(code, &weights, has_children.then_some(&path));
+        }
+
+        let weights = match self.sequences.allkeys_weights(code) {
+            Some(weights) => weights.to_vec(),
+            None => self.sequences.collate(decomposition),
+        };
+
+        // только стартеры - веса не зависят от следующих кодпоинтов
+        if decomposition
+            .iter()
+            .all(|&code| self.unicode.ccc(code) == 0)
+        {
+            return self.starter(code, &weights, None);
+        }
+
+        // узел с весами кодпоинта, за ним - список элементов декомпозиции
+        let pos = self.trie_pos()?;
+        let last_ccc = self
+            .unicode
+            .ccc_ordinal(decomposition[decomposition.len() - 1]) as u64;
+
+        self.write_node(code, 0, &weights, true, false)?;
+        self.write_list(decomposition)?;
+
+        Ok(MARKER_STARTER_DECOMPOSITION as u64 | pos << 4 | last_ccc << 20)
+    }
+
+    /// стартер: одинарные веса, расширение или начало последовательности
+    fn starter(
+        &mut self,
+        code: u32,
+        weights: &[u32],
+        path: Option<&[u32]>,
+    ) -> Result<u64, BuildError>
+    {
+        if let Some(path) = path {
+            let pos = self.trie_pos()?;
+
+            self.write_node(code, 0, weights, true, true)?;
+            self.write_children(path)?;
+
+            return Ok(MARKER_STARTER_TRIE as u64 | pos << 4);
+        }
+
+        match weights {
+            [weights] => Ok(MARKER_STARTER_SINGLE_WEIGHTS as u64 | (*weights as u64) << 4),
+            _ => self.expansion(weights),
+        }
+    }
+
+    /// нестартер - расширение, начало последовательности, или декомпозиция, начинающаяся с
+    /// нестартера
+    fn nonstarters(&mut self, codes: &[u32]) -> Result<u64, BuildError>
+    {
+        let pos = self.trie_pos()?;
+        let last_ccc = self.unicode.ccc_ordinal(codes[codes.len() - 1]) as u64;
+
+        self.write_list(codes)?;
+
+        Ok(MARKER_NONSTARTER_TRIE as u64 | pos << 4 | last_ccc << 20)
+    }
+
+    /// расширение
+    fn expansion(&mut self, weights: &[u32]) -> Result<u64, BuildError>
+    {
+        let pos = self.expansions.len();
+
+        if weights.len() > u8::MAX as usize {
+            return Err(BuildError::TableTooLarge("expansion"));
+        }
+
+        // конец расширения коллатор вычисляет в u16
+        if pos + weights.len() > u16::MAX as usize {
+            return Err(BuildError::TableTooLarge("expansions"));
+        }
+
+        self.expansions.extend_from_slice(weights);
+
+        Ok(MARKER_STARTER_EXPANSION as u64 | (pos as u64) << 4 | (weights.len() as u64) << 20)
+    }
+
+    /// позиция следующего узла бора
+    fn trie_pos(&self) -> Result<u64, BuildError>
+    {
+        match self.tries.len() > u16::MAX as usize {
+            true => Err(BuildError::TableTooLarge("tries")),
+            false => Ok(self.tries.len() as u64),
+        }
+    }
+
+    /// записать список узлов - кодпоинтов с их собственными весами и поддеревьями
+    fn write_list(&mut self, codes: &[u32]) -> Result<(), BuildError>
+    {
+        for (i, &code) in codes.iter().enumerate() {
+            let weights = self.sequences.single_weights(code);
+            let has_children = self.sequences.has_children(&[code]);
+
+            self.write_node(
+                code,
+                self.unicode.ccc_ordinal(code),
+                &weights,
+                i == codes.len() - 1,
+                has_children,
+            )?;
+
+            if has_children {
+                self.write_children(&[code])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// записать потомков узла вместе с их поддеревьями
+    fn write_children(&mut self, path: &[u32]) -> Result<(), BuildError>
+    {
+        let children = self.sequences.children(path);
+
+        for (i, child) in children.iter().enumerate() {
+            let weights = self.sequences.weights(&child.path);
+
+            self.write_node(
+                child.code,
+                self.unicode.ccc_ordinal(child.code),
+                &weights,
+                i == children.len() - 1,
+                child.is_branch,
+            )?;
+
+            if child.is_branch {
+                self.write_children(&child.path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// записать узел бора
+    fn write_node(
+        &mut self,
+        code: u32,
+        ccc_ordinal: u8,
+        weights: &[u32],
+        is_last: bool,
+        has_children: bool,
+    ) -> Result<(), BuildError>
+    {
+        if code > 0x3FFFF {
+            return Err(BuildError::CodepointOutOfRange(code));
+        }
+
+        if weights.len() > 0x3F {
+            return Err(BuildError::TableTooLarge("trie node"));
+        }
+
+        if ccc_ordinal as usize > MAX_CCC_ORDINAL {
+            return Err(BuildError::TooManyCccClasses(ccc_ordinal as usize + 1));
+        }
+
+        self.tries.push(
+            (weights.len() as u32) << 26
+                | code << 8
+                | (ccc_ordinal as u32) << 2
+                | (is_last as u32) << 1
+                | has_children as u32,
+        );
+        self.tries.extend_from_slice(weights);
+
+        Ok(())
+    }
+}
+
+/// индекс, scalars64, scalars32
+type Index = (Vec<u16>, Vec<u64>, Vec<u32>);
+
+/// индекс и блоки данных: одинаковые блоки и группы блоков записываются один раз
+fn build_index(values: &HashMap<u32, u64>) -> Result<Index, BuildError>
+{
+    let mut index = vec![0; INDEX_GROUPS_START];
+    let mut scalars64 = vec![];
+    let mut scalars32 = vec![];
+
+    let mut blocks64 = HashMap::new();
+    let mut blocks32 = HashMap::new();
+    let mut groups = HashMap::new();
+
+    let group_indices =
+        (0 ..= LAST_CODEPOINT >> 7).chain(IGNORABLES.start >> 7 .. IGNORABLES.end >> 7);
+
+    for group_index in group_indices {
+        let mut group = [0u16; 16];
+
+        for (block_index, entry) in group.iter_mut().enumerate() {
+            let start = (group_index << 7) | (block_index as u32) << 3;
+            let block: Vec<u64> = (start .. start + 8)
+                .map(|code| values.get(&code).copied().unwrap_or(0))
+                .collect();
+
+            let (pos, flag) = match block.iter().any(|&value| value > u32::MAX as u64) {
+                true => {
+                    let pos = *blocks64.entry(block.clone()).or_insert_with(|| {
+                        scalars64.extend_from_slice(&block);
+                        scalars64.len() - 8
+                    });
+                    (pos, 1)
+                }
+                false => {
+                    let pos = *blocks32.entry(block.clone()).or_insert_with(|| {
+                        scalars32.extend(block.iter().map(|&value| value as u32));
+                        scalars32.len() - 8
+                    });
+                    (pos, 0)
+                }
+            };
+
+            if pos > (u16::MAX >> 1) as usize {
+                return Err(BuildError::TableTooLarge("scalars"));
+            }
+
+            *entry = (pos << 1) as u16 | flag;
+        }
+
+        // блоки кодпоинтов до CONTINUOUS_BLOCK_END должны идти последовательно
+        let base = match group_index <= CONTINUOUS_BLOCK_END >> 7 {
+            true => {
+                let base = index.len();
+
+                groups.entry(group).or_insert(base);
+                index.extend_from_slice(&group);
+                base
+            }
+            false => *groups.entry(group).or_insert_with(|| {
+                index.extend_from_slice(&group);
+                index.len() - 16
+            }),
+        };
+
+        if base > u16::MAX as usize {
+            return Err(BuildError::TableTooLarge("index"));
+        }
+
+        let slot = match group_index > LAST_CODEPOINT >> 7 {
+            true => group_index - IGNORABLES_SHIFT,
+            false => group_index,
+        };
+
+        index[slot as usize] = base as u16;
+    }
+
+    // пустая таблица не пройдёт проверку (DataError::EmptyTable), даже если записи в ней не нужны
+    if scalars64.is_empty() {
+        scalars64.push(0);
+    }
+
+    Ok((index, scalars64, scalars32))
+}
@@ -0,0 +1,174 @@
+// разбор UnicodeData.txt: нужны только CCC (поле 3) и канонические декомпозиции (поле 5)
+//
+// диапазоны (<..., First> / <..., Last>) не имеют ни того, ни другого и пропускаются
+
+use std::collections::HashMap;
+
+use crate::allkeys::parse_codes;
+use crate::BuildError;
+
+/// слоги хангыль
+const HANGUL_SYLLABLES: core::ops::RangeInclusive<u32> = 0xAC00 ..= 0xD7A3;
+/// максимальный порядковый номер CCC - 6 бит в узле бора
+pub const MAX_CCC_ORDINAL: usize = 0x3F;
+
+/// CCC и канонические декомпозиции кодпоинтов
+pub struct UnicodeData
+{
+    /// CCC нестартеров
+    ccc: HashMap<u32, u8>,
+    /// канонические декомпозиции (не полные - как записаны в UnicodeData)
+    decompositions: HashMap<u32, Vec<u32>>,
+    /// порядковые номера классов CCC - в таблицах записываются они, а не сами CCC
+    ccc_ordinals: [u8; 256],
+}
+
+impl UnicodeData
+{
+    /// разобрать содержимое UnicodeData.txt
+    pub fn parse(source: &str) -> Result<Self, BuildError>
+    {
+        let mut ccc = HashMap::new();
+        let mut decompositions = HashMap::new();
+
+        for (line, text) in (1 ..).zip(source.lines()) {
+            if text.trim().is_empty() {
+                continue;
+            }
+
+            let fields: Vec<&str> = text.split(';').collect();
+
+            if fields.len() < 6 {
+                return Err(BuildError::InvalidUnicodeData(line));
+            }
+
+            let code = u32::from_str_radix(fields[0], 16)
+                .map_err(|_| BuildError::InvalidUnicodeData(line))?;
+
+            let code_ccc: u8 = fields[3]
+                .parse()
+                .map_err(|_| BuildError::InvalidUnicodeData(line))?;
+
+            if code_ccc != 0 {
+                ccc.insert(code, code_ccc);
+            }
+
+            // совместимые декомпозиции (<compat>, <font> и т.д.) в UCA не участвуют
+            if !fields[5].is_empty() && !fields[5].starts_with('<') {
+                let decomposition =
+                    parse_codes(fields[5]).ok_or(BuildError::InvalidUnicodeData(line))?;
+
+                decompositions.insert(code, decomposition);
+            }
+        }
+
+        let mut classes: Vec<u8> = ccc.values().copied().chain([0]).collect();
+
+        classes.sort_unstable();
+        classes.dedup();
+
+        if classes.len() > MAX_CCC_ORDINAL + 1 {
+            return Err(BuildError::TooManyCccClasses(classes.len()));
+        }
+
+        let mut ccc_ordinals = [0; 256];
+
+        for (ordinal, &class) in classes.iter().enumerate() {
+            ccc_ordinals[class as usize] = ordinal as u8;
+        }
+
+        Ok(Self {
+            ccc,
+            decompositions,
+            ccc_ordinals,
+        })
+    }
+
+    /// CCC кодпоинта
+    pub fn ccc(&self, code: u32) -> u8
+    {
+        self.ccc.get(&code).copied().unwrap_or(0)
+    }
+
+    /// порядковый номер CCC кодпоинта
+    pub fn ccc_ordinal(&self, code: u32) -> u8
+    {
+        self.ccc_ordinals[self.ccc(code) as usize]
+    }
+
+    /// кодпоинты с ненулевым CCC, по возрастанию
+    pub fn nonstarters(&self) -> Vec<u32>
+    {
+        let mut codes: Vec<u32> = self.ccc.keys().copied().collect();
+
+        codes.sort_unstable();
+        codes
+    }
+
+    /// кодпоинты, имеющие каноническую декомпозицию, по возрастанию
+    pub fn decomposed_codepoints(&self) -> Vec<u32>
+    {
+        let mut codes: Vec<u32> = self.decompositions.keys().copied().collect();
+
+        codes.sort_unstable();
+        codes
+    }
+
+    /// последовательность кодпоинтов в форме NFD
+    pub fn nfd(&self, codes: &[u32]) -> Vec<u32>
+    {
+        let mut result = Vec::with_capacity(codes.len());
+
+        for &code in codes {
+            self.decompose(code, &mut result);
+        }
+
+        // сортируем последовательности нестартеров по CCC (сортировка стабильная)
+        let mut start = 0;
+
+        while start < result.len() {
+            if self.ccc(result[start]) == 0 {
+                start += 1;
+                continue;
+            }
+
+            let mut end = start + 1;
+
+            while end < result.len() && self.ccc(result[end]) != 0 {
+                end += 1;
+            }
+
+            result[start .. end].sort_by_key(|&code| self.ccc(code));
+
+            start = end;
+        }
+
+        result
+    }
+
+    /// полная каноническая декомпозиция кодпоинта
+    fn decompose(&self, code: u32, result: &mut Vec<u32>)
+    {
+        if HANGUL_SYLLABLES.contains(&code) {
+            let index = code - HANGUL_SYLLABLES.start();
+
+            result.push(0x1100 + index / 588);
+            result.push(0x1161 + (index % 588) / 28);
+
+            if !index.is_multiple_of(28) {
+                result.push(0x11A7 + index % 28);
+            }
+
+            return;
+        }
+
+        match self.decompositions.get(&code) {
+            Some(decomposition) => {
+                for &code in decomposition {
+                    self.decompose(code, result);
+                }
+            }
+            None => result.push(code),
+        }
+    }
+}
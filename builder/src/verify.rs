@@ -0,0 +1,93 @@
+// сравнение собранных таблиц с эталонными (например, с data/cldr_und.txt): расположение данных и
+// веса каждого кодпоинта - расхождения в расположении не обязательно меняют веса
+
+use std::sync::Arc;
+
+use unicode_collator::options::CollatorOptions;
+use unicode_collator::{Collator, CollatorData};
+
+use crate::Tables;
+
+/// результат сравнения
+#[derive(Debug, Default)]
+pub struct Comparison
+{
+    /// отличающиеся таблицы и позиция первого отличающегося элемента
+    pub tables: Vec<(&'static str, usize)>,
+    /// кодпоинты, веса которых отличаются
+    pub codepoints: Vec<u32>,
+}
+
+impl Comparison
+{
+    /// таблицы совпадают полностью
+    pub fn is_identical(&self) -> bool
+    {
+        self.tables.is_empty() && self.codepoints.is_empty()
+    }
+}
+
+/// сравнить таблицы (обе должны быть проверены - см. build, Tables::from_source)
+pub fn compare(tables: &Tables, expected: &Tables) -> Comparison
+{
+    let differences = [
+        ("index", first_difference(&tables.index, &expected.index)),
+        (
+            "scalars64",
+            first_difference(&tables.scalars64, &expected.scalars64),
+        ),
+        (
+            "scalars32",
+            first_difference(&tables.scalars32, &expected.scalars32),
+        ),
+        (
+            "expansions",
+            first_difference(&tables.expansions, &expected.expansions),
+        ),
+        ("tries", first_difference(&tables.tries, &expected.tries)),
+        (
+            "continuous_block_end",
+            (tables.continuous_block_end != expected.continuous_block_end).then_some(0),
+        ),
+    ];
+
+    let collator = default_collator(tables);
+    let expected_collator = default_collator(expected);
+
+    let mut buffer = [0; 4];
+
+    Comparison {
+        tables: differences
+            .into_iter()
+            .filter_map(|(name, pos)| pos.map(|pos| (name, pos)))
+            .collect(),
+        codepoints: (0 ..= 0x10FFFF)
+            .filter_map(char::from_u32)
+            .filter(|c| {
+                let input = c.encode_utf8(&mut buffer);
+                collator.get_weights(input) != expected_collator.get_weights(input)
+            })
+            .map(u32::from)
+            .collect(),
+    }
+}
+
+/// коллатор с опциями по умолчанию - их поддерживают любые таблицы
+fn default_collator(tables: &Tables) -> Collator<'_>
+{
+    let data = CollatorData::from_baked(tables.as_weights_data());
+
+    match Collator::from_data(Arc::new(data), CollatorOptions::default()) {
+        Ok(collator) => collator,
+        Err(_) => unreachable!(),
+    }
+}
+
+/// позиция первого отличающегося элемента
+fn first_difference<T: PartialEq>(values: &[T], expected: &[T]) -> Option<usize>
+{
+    match values.iter().zip(expected).position(|(a, b)| a != b) {
+        None if values.len() != expected.len() => Some(values.len().min(expected.len())),
+        pos => pos,
+    }
+}
@@ -1,4 +1,5 @@
 use super::CodepointWithData;
+use crate::numeric::digit_value;
 
 /// блок последнего кодпоинта с весами / декомпозицией (U+2FA1D)
 const LAST_CODEPOINT_BLOCK: u16 = (0x2FA1D >> (18 - 11)) as u16;
@@ -19,6 +20,8 @@ pub struct CodepointsIter<'a>
     index: &'a [u16],
     /// с U+0000 и до этого кодпоинта включительно блоки в data идут последовательно
     continuous_block_end: u32,
+    /// итерация останавливается перед десятичной цифрой (см. take_digits)
+    stop_at_digits: bool,
 }
 
 impl<'a> Iterator for CodepointsIter<'a>
@@ -29,7 +32,14 @@ impl<'a> Iterator for CodepointsIter<'a>
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item>
     {
+        let rest = self.iter.clone();
         let code = self.iter.next()? as u32;
+
+        if self.stop_at_digits && digit_value(code).is_some() {
+            self.iter = rest;
+            return None;
+        }
+
         let data = self.get_data_value(code);
 
         Some(CodepointWithData { data, code })
@@ -62,7 +72,7 @@ impl<'a> CodepointsIter<'a>
         };
 
         let code_offsets = (code as u16) & 0x7F;
-        let data_block_index = data_block_base | (code_offsets >> 3) as u16;
+        let data_block_index = data_block_base | (code_offsets >> 3);
 
         let index = self.index[data_block_index as usize];
         let data_index = ((index >> 1) | code_offsets & 0x7) as usize;
@@ -73,6 +83,38 @@ impl<'a> CodepointsIter<'a>
         }
     }
 
+    /// запись о кодпоинте с проверкой границ таблиц (см. validate), None - выход за границы
+    pub fn checked_data_value(&self, code: u32) -> Option<u64>
+    {
+        let data_block_base = match code <= self.continuous_block_end {
+            true => 0x600 | (((code >> 3) as u16) & !0xF),
+            false => {
+                let mut group_index = (code >> 7) as u16;
+
+                if group_index > LAST_CODEPOINT_BLOCK {
+                    if IGNORABLES_BLOCKS.contains(&group_index) {
+                        group_index -= IGNORABLES_SHIFT;
+                    } else {
+                        return Some(0);
+                    }
+                };
+
+                *self.index.get(group_index as usize)?
+            }
+        };
+
+        let code_offsets = (code as u16) & 0x7F;
+        let data_block_index = data_block_base | (code_offsets >> 3);
+
+        let index = *self.index.get(data_block_index as usize)?;
+        let data_index = ((index >> 1) | code_offsets & 0x7) as usize;
+
+        match index & 1 != 0 {
+            true => self.scalars64.get(data_index).copied(),
+            false => self.scalars32.get(data_index).map(|&value| value as u64),
+        }
+    }
+
     /// получить следующий кодпоинт, в случае None - выполнить код
     #[inline(always)]
     pub fn next_or_else<F>(&mut self, on_none: F) -> Option<CodepointWithData>
@@ -88,13 +130,33 @@ impl<'a> CodepointsIter<'a>
         }
     }
 
+    /// получить последовательность десятичных цифр, с которой продолжается строка
+    /// (пустая строка - строка закончилась)
+    #[inline(always)]
+    pub fn take_digits(&mut self) -> &'a str
+    {
+        let rest = self.iter.as_str();
+
+        let len = rest
+            .char_indices()
+            .find(|&(_, c)| digit_value(c as u32).is_none())
+            .map_or(rest.len(), |(len, _)| len);
+
+        self.iter = rest[len ..].chars();
+
+        &rest[.. len]
+    }
+
     /// итератор по кодпоинтам строки, с данными о весах, декомпозиции, последовательностях
+    ///
+    /// stop_at_digits - итератор останавливается перед десятичными цифрами (числовое сопоставление)
     pub fn new(
         input: &'a str,
         scalars64: &'a [u64],
         scalars32: &'a [u32],
         index: &'a [u16],
         continuous_block_end: u32,
+        stop_at_digits: bool,
     ) -> Self
     {
         Self {
@@ -103,6 +165,7 @@ impl<'a> CodepointsIter<'a>
             scalars32,
             index,
             continuous_block_end,
+            stop_at_digits,
         }
     }
 }
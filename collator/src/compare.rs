@@ -0,0 +1,153 @@
+use core::cmp::Ordering;
+
+use crate::key::{arrange_level, entry_level, entry_weights, is_collected_level, levels_count};
+use crate::nfd::nfd;
+use crate::options::Strength;
+use crate::stream::WeightsStream;
+use crate::weights::Weights;
+use crate::Collator;
+
+impl<'a> Collator<'a>
+{
+    /// сравнить строки без построения ключей
+    ///
+    /// веса обеих строк вычисляются по мере необходимости, сравнение идёт по уровням и прекращается
+    /// на первом различии. результат совпадает с compare_keys для ключей, полученных из get_key
+    pub fn compare(&self, a: &str, b: &str) -> Ordering
+    {
+        if a == b {
+            return Ordering::Equal;
+        }
+
+        let mut a_weights = LazyWeights::new(self.weights_stream(a), a.len());
+        let mut b_weights = LazyWeights::new(self.weights_stream(b), b.len());
+
+        // первичный уровень сравниваем по мере вычисления весов, к остальным уровням
+        // переходим только если первичные веса совпали - значит, веса вычислены полностью
+        for level in 0 .. levels_count(&self.options) as usize {
+            let level = entry_level(level, &self.options);
+
+            let mut a_cursor = LevelCursor::default();
+            let mut b_cursor = LevelCursor::default();
+
+            // веса уровня в обратном порядке или с отбрасываемыми конечными весами - сравниваем
+            // уровни целиком
+            if is_collected_level(level, &self.options) {
+                let mut a_level = a_cursor.collect(self, &mut a_weights, level);
+                let mut b_level = b_cursor.collect(self, &mut b_weights, level);
+
+                arrange_level(&mut a_level, level, &self.options);
+                arrange_level(&mut b_level, level, &self.options);
+
+                match a_level.cmp(&b_level) {
+                    Ordering::Equal => continue,
+                    ordering => return ordering,
+                }
+            }
+
+            loop {
+                let a_weight = a_cursor.next(self, &mut a_weights, level);
+                let b_weight = b_cursor.next(self, &mut b_weights, level);
+
+                match (a_weight, b_weight) {
+                    (None, None) => break,
+                    (None, Some(_)) => return Ordering::Less,
+                    (Some(_), None) => return Ordering::Greater,
+                    (Some(a_weight), Some(b_weight)) => {
+                        if a_weight != b_weight {
+                            return a_weight.cmp(&b_weight);
+                        }
+                    }
+                }
+            }
+        }
+
+        // идентичный уровень - сравниваем кодпоинты NFD-форм строк
+        if self.options.strength == Strength::Identical {
+            return nfd(a).cmp(&nfd(b));
+        }
+
+        Ordering::Equal
+    }
+}
+
+/// веса строки, вычисляемые по мере необходимости
+struct LazyWeights<'a>
+{
+    stream: WeightsStream<'a>,
+    weights: Vec<u32>,
+}
+
+impl<'a> LazyWeights<'a>
+{
+    #[inline(always)]
+    fn new(stream: WeightsStream<'a>, capacity: usize) -> Self
+    {
+        Self {
+            stream,
+            weights: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// веса с индексом index, None - строка закончилась раньше
+    #[inline(always)]
+    fn get(&mut self, collator: &Collator, index: usize) -> Option<Weights>
+    {
+        while index >= self.weights.len() {
+            if self.stream.is_finished {
+                return None;
+            }
+
+            collator.weights_loop(&mut self.stream, &mut self.weights, index + 1);
+        }
+
+        Some(Weights::from(self.weights[index]))
+    }
+}
+
+/// позиция в последовательности весов одного уровня
+#[derive(Default)]
+struct LevelCursor
+{
+    /// индекс следующего элемента
+    index: usize,
+    /// состояние обработки переменных весов
+    following_a_variable: bool,
+}
+
+impl LevelCursor
+{
+    /// следующий ненулевой вес уровня
+    #[inline(always)]
+    fn next(&mut self, collator: &Collator, source: &mut LazyWeights, level: usize) -> Option<u16>
+    {
+        loop {
+            let entry = source.get(collator, self.index)?;
+            self.index += 1;
+
+            let weight = entry_weights(
+                &entry,
+                &collator.options,
+                &collator.reordering,
+                &mut self.following_a_variable,
+            )[level];
+
+            if weight != 0 {
+                return Some(weight);
+            }
+        }
+    }
+
+    /// все оставшиеся веса уровня
+    #[inline(always)]
+    fn collect(&mut self, collator: &Collator, source: &mut LazyWeights, level: usize) -> Vec<u16>
+    {
+        let mut result = vec![];
+
+        while let Some(weight) = self.next(collator, source, level) {
+            result.push(weight);
+        }
+
+        result
+    }
+}
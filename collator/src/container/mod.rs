@@ -0,0 +1,350 @@
+// двоичный контейнер таблиц весов (WeightsData) - для загрузки таблиц во время выполнения,
+// в том числе из файла, отображённого в память. все числа - little-endian
+//
+// заголовок, 48 байт:
+//
+// 0 ..  4      магическое число "UCOL"
+// 4 ..  6      версия формата (FORMAT_VERSION)
+// 6 ..  8      зарезервировано, 0
+// 8 .. 11      версия Unicode: major, minor, update (0 - не указана)
+// 11           зарезервировано, 0
+// 12 .. 14     версия CLDR: major, minor (0 - не указана)
+// 14 .. 16     зарезервировано, 0
+// 16 .. 20     continuous_block_end
+// 20 .. 40     длины таблиц (количество элементов, u32): scalars64, scalars32, expansions, tries,
+//              index
+// 40 .. 44     CRC-32 (IEEE) всех байт контейнера, кроме самого поля контрольной суммы
+// 44 .. 48     зарезервировано, 0
+//
+// затем таблицы в том же порядке, каждая начинается со смещения, кратного 16, промежутки
+// заполнены нулями. контейнер заканчивается последней таблицей, дополненной нулями до кратной 16
+// длины
+//
+// таблицы читаются без копирования, поэтому начало контейнера должно быть выровнено по 8 байтам
+// (например, файл, отображённый в память, или буфер из u64)
+
+use core::fmt;
+use core::mem::{align_of, size_of};
+use std::io;
+
+use crate::data::WeightsData;
+use crate::options::OptionsError;
+use crate::validate::DataError;
+
+/// магическое число контейнера
+pub const MAGIC: [u8; 4] = *b"UCOL";
+/// текущая версия формата контейнера
+pub const FORMAT_VERSION: u16 = 1;
+/// размер заголовка
+pub const HEADER_SIZE: usize = 48;
+/// выравнивание таблиц
+const TABLE_ALIGN: usize = 16;
+
+/// версии Unicode и CLDR, по данным которых построены таблицы (0 - версия не указана)
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct DataVersion
+{
+    /// major, minor, update
+    pub unicode: [u8; 3],
+    /// major, minor
+    pub cldr: [u8; 2],
+}
+
+/// ошибка чтения контейнера
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContainerError
+{
+    /// неверное магическое число
+    InvalidMagic,
+    /// неизвестная версия формата
+    UnknownVersion(u16),
+    /// длина данных не соответствует заголовку
+    InvalidLength
+    {
+        expected: usize, actual: usize
+    },
+    /// ненулевые зарезервированные поля заголовка
+    InvalidHeader,
+    /// начало контейнера не выровнено по 8 байтам
+    Misaligned,
+    /// контрольная сумма не совпадает
+    ChecksumMismatch
+    {
+        expected: u32, actual: u32
+    },
+    /// чтение без копирования возможно только на little-endian платформах
+    UnsupportedEndianness,
+    /// таблицы контейнера не прошли проверку (см. Collator::from_bytes)
+    InvalidData(DataError),
+    /// таблицы контейнера не поддерживают опции коллатора (см. Collator::from_bytes)
+    UnsupportedOptions(OptionsError),
+}
+
+impl fmt::Display for ContainerError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            Self::InvalidMagic => write!(f, "неверное магическое число контейнера"),
+            Self::UnknownVersion(version) => {
+                write!(f, "неизвестная версия формата контейнера: {}", version)
+            }
+            Self::InvalidLength { expected, actual } => write!(
+                f,
+                "некорректная длина контейнера: {}, ожидалось {}",
+                actual, expected
+            ),
+            Self::InvalidHeader => write!(f, "некорректный заголовок контейнера"),
+            Self::Misaligned => write!(f, "данные контейнера не выровнены по 8 байтам"),
+            Self::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "контрольная сумма не совпадает: {:#010X}, ожидалось {:#010X}",
+                actual, expected
+            ),
+            Self::UnsupportedEndianness => {
+                write!(
+                    f,
+                    "контейнер можно прочитать только на little-endian платформе"
+                )
+            }
+            Self::InvalidData(error) => write!(f, "некорректные таблицы весов: {}", error),
+            Self::UnsupportedOptions(error) => write!(f, "опции не поддерживаются: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for ContainerError {}
+
+/// заголовок контейнера
+struct Header
+{
+    version: DataVersion,
+    continuous_block_end: u32,
+    /// длины таблиц: scalars64, scalars32, expansions, tries, index
+    lengths: [u32; 5],
+    checksum: u32,
+}
+
+/// размеры элементов таблиц в порядке их записи
+const ELEMENT_SIZES: [usize; 5] = [
+    size_of::<u64>(),
+    size_of::<u32>(),
+    size_of::<u32>(),
+    size_of::<u32>(),
+    size_of::<u16>(),
+];
+
+/// прочитать таблицы из контейнера без копирования (содержимое таблиц не проверяется, см.
+/// Collator::from_bytes)
+pub fn read(bytes: &[u8]) -> Result<(WeightsData<'_>, DataVersion), ContainerError>
+{
+    if cfg!(target_endian = "big") {
+        return Err(ContainerError::UnsupportedEndianness);
+    }
+
+    let header = read_header(bytes)?;
+    let offsets = table_offsets(&header.lengths);
+    let expected = offsets[5];
+
+    if bytes.len() != expected {
+        return Err(ContainerError::InvalidLength {
+            expected,
+            actual: bytes.len(),
+        });
+    }
+
+    if !(bytes.as_ptr() as usize).is_multiple_of(align_of::<u64>()) {
+        return Err(ContainerError::Misaligned);
+    }
+
+    let actual = checksum(bytes);
+
+    if actual != header.checksum {
+        return Err(ContainerError::ChecksumMismatch {
+            expected: header.checksum,
+            actual,
+        });
+    }
+
+    let lengths = header.lengths.map(|len| len as usize);
+
+    // смещения кратны 16, начало выровнено по 8 байтам, длины проверены, порядок байт совпадает
+    let data = unsafe {
+        WeightsData {
+            scalars64: cast_slice(bytes, offsets[0], lengths[0]),
+            scalars32: cast_slice(bytes, offsets[1], lengths[1]),
+            expansions: cast_slice(bytes, offsets[2], lengths[2]),
+            tries: cast_slice(bytes, offsets[3], lengths[3]),
+            index: cast_slice(bytes, offsets[4], lengths[4]),
+            continuous_block_end: header.continuous_block_end,
+        }
+    };
+
+    Ok((data, header.version))
+}
+
+/// версия данных контейнера (проверяется только заголовок)
+pub fn read_version(bytes: &[u8]) -> Result<DataVersion, ContainerError>
+{
+    read_header(bytes).map(|header| header.version)
+}
+
+/// записать таблицы в контейнер
+pub fn write<W: io::Write>(
+    writer: &mut W,
+    data: &WeightsData,
+    version: DataVersion,
+) -> io::Result<()>
+{
+    writer.write_all(&to_bytes(data, version))
+}
+
+/// таблицы в виде контейнера
+pub fn to_bytes(data: &WeightsData, version: DataVersion) -> Vec<u8>
+{
+    let lengths = [
+        data.scalars64.len(),
+        data.scalars32.len(),
+        data.expansions.len(),
+        data.tries.len(),
+        data.index.len(),
+    ]
+    .map(|len| u32::try_from(len).expect("длина таблицы превышает u32::MAX"));
+
+    let offsets = table_offsets(&lengths);
+    let mut bytes = vec![0; offsets[5]];
+
+    let scalars64 = data.scalars64.iter().flat_map(|value| value.to_le_bytes());
+    let scalars32 = data.scalars32.iter().flat_map(|value| value.to_le_bytes());
+    let expansions = data.expansions.iter().flat_map(|value| value.to_le_bytes());
+    let tries = data.tries.iter().flat_map(|value| value.to_le_bytes());
+    let index = data.index.iter().flat_map(|value| value.to_le_bytes());
+
+    write_table(&mut bytes[offsets[0] ..], scalars64);
+    write_table(&mut bytes[offsets[1] ..], scalars32);
+    write_table(&mut bytes[offsets[2] ..], expansions);
+    write_table(&mut bytes[offsets[3] ..], tries);
+    write_table(&mut bytes[offsets[4] ..], index);
+
+    bytes[0 .. 4].copy_from_slice(&MAGIC);
+    bytes[4 .. 6].copy_from_slice(&FORMAT_VERSION.to_le_bytes());
+    bytes[8 .. 11].copy_from_slice(&version.unicode);
+    bytes[12 .. 14].copy_from_slice(&version.cldr);
+    bytes[16 .. 20].copy_from_slice(&data.continuous_block_end.to_le_bytes());
+
+    for (i, len) in lengths.iter().enumerate() {
+        bytes[20 + i * 4 .. 24 + i * 4].copy_from_slice(&len.to_le_bytes());
+    }
+
+    let checksum = checksum(&bytes);
+
+    bytes[40 .. 44].copy_from_slice(&checksum.to_le_bytes());
+
+    bytes
+}
+
+/// прочитать и проверить заголовок
+fn read_header(bytes: &[u8]) -> Result<Header, ContainerError>
+{
+    if bytes.len() < HEADER_SIZE {
+        return Err(ContainerError::InvalidLength {
+            expected: HEADER_SIZE,
+            actual: bytes.len(),
+        });
+    }
+
+    if bytes[0 .. 4] != MAGIC {
+        return Err(ContainerError::InvalidMagic);
+    }
+
+    let u16_at = |pos: usize| u16::from_le_bytes([bytes[pos], bytes[pos + 1]]);
+    let u32_at = |pos: usize| u32::from_le_bytes(bytes[pos .. pos + 4].try_into().unwrap());
+
+    let format_version = u16_at(4);
+
+    if format_version != FORMAT_VERSION {
+        return Err(ContainerError::UnknownVersion(format_version));
+    }
+
+    if u16_at(6) != 0 || bytes[11] != 0 || u16_at(14) != 0 || u32_at(44) != 0 {
+        return Err(ContainerError::InvalidHeader);
+    }
+
+    Ok(Header {
+        version: DataVersion {
+            unicode: [bytes[8], bytes[9], bytes[10]],
+            cldr: [bytes[12], bytes[13]],
+        },
+        continuous_block_end: u32_at(16),
+        lengths: [u32_at(20), u32_at(24), u32_at(28), u32_at(32), u32_at(36)],
+        checksum: u32_at(40),
+    })
+}
+
+/// смещения таблиц и длина контейнера (последний элемент)
+fn table_offsets(lengths: &[u32; 5]) -> [usize; 6]
+{
+    let mut offsets = [HEADER_SIZE; 6];
+
+    for i in 0 .. 5 {
+        let size = lengths[i] as usize * ELEMENT_SIZES[i];
+
+        offsets[i + 1] = (offsets[i] + size).next_multiple_of(TABLE_ALIGN);
+    }
+
+    offsets
+}
+
+/// записать байты таблицы в начало слайса
+fn write_table(target: &mut [u8], table: impl Iterator<Item = u8>)
+{
+    for (byte, value) in target.iter_mut().zip(table) {
+        *byte = value;
+    }
+}
+
+/// слайс байт как слайс чисел
+///
+/// смещение и длина должны быть в пределах слайса, адрес - выровнен для T
+unsafe fn cast_slice<T>(bytes: &[u8], offset: usize, len: usize) -> &[T]
+{
+    core::slice::from_raw_parts(bytes.as_ptr().add(offset) as *const T, len)
+}
+
+/// таблица CRC-32 (IEEE, полином 0xEDB88320)
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut value = i as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            value = match value & 1 {
+                1 => (value >> 1) ^ 0xEDB88320,
+                _ => value >> 1,
+            };
+            bit += 1;
+        }
+
+        table[i] = value;
+        i += 1;
+    }
+
+    table
+};
+
+/// контрольная сумма контейнера: заголовок без поля контрольной суммы и таблицы
+fn checksum(bytes: &[u8]) -> u32
+{
+    crc32(bytes[.. 40].iter().chain(&bytes[44 ..]))
+}
+
+/// контрольная сумма CRC-32
+fn crc32<'a>(bytes: impl Iterator<Item = &'a u8>) -> u32
+{
+    !bytes.fold(!0, |crc, &byte| {
+        CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
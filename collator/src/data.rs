@@ -1,3 +1,5 @@
+use crate::slice::aligned::AlignedArray;
+
 /// данные весов
 pub struct WeightsData<'a>
 {
@@ -16,7 +18,48 @@ pub struct WeightsData<'a>
     pub continuous_block_end: u32,
 }
 
-pub fn cldr_und<'a>() -> WeightsData<'a>
+/// исходные таблицы CLDR (root) - используются только при компиляции, см. cldr_und
+const CLDR_UND: WeightsData<'static> = include!("./../../data/cldr_und.txt");
+
+static CLDR_UND_INDEX: AlignedArray<u16, { CLDR_UND.index.len() }> =
+    AlignedArray::from_slice(CLDR_UND.index, 0);
+static CLDR_UND_SCALARS32: AlignedArray<u32, { CLDR_UND.scalars32.len() }> =
+    AlignedArray::from_slice(CLDR_UND.scalars32, 0);
+static CLDR_UND_SCALARS64: AlignedArray<u64, { CLDR_UND.scalars64.len() }> =
+    AlignedArray::from_slice(CLDR_UND.scalars64, 0);
+static CLDR_UND_EXPANSIONS: AlignedArray<u32, { CLDR_UND.expansions.len() }> =
+    AlignedArray::from_slice(CLDR_UND.expansions, 0);
+static CLDR_UND_TRIES: AlignedArray<u32, { CLDR_UND.tries.len() }> =
+    AlignedArray::from_slice(CLDR_UND.tries, 0);
+
+/// таблицы CLDR (root), выровненные при компиляции
+pub const fn cldr_und() -> WeightsData<'static>
+{
+    WeightsData {
+        index: &CLDR_UND_INDEX.0,
+        scalars32: &CLDR_UND_SCALARS32.0,
+        scalars64: &CLDR_UND_SCALARS64.0,
+        expansions: &CLDR_UND_EXPANSIONS.0,
+        tries: &CLDR_UND_TRIES.0,
+        continuous_block_end: CLDR_UND.continuous_block_end,
+    }
+}
+
+/// данные канонической декомпозиции (NFD), используются для идентичного уровня
+pub struct NormalizationData<'a>
+{
+    /// кодпоинты с ненулевым CCC: (кодпоинт << 8) | CCC, по возрастанию
+    pub ccc: &'a [u32],
+    /// кодпоинты, имеющие каноническую декомпозицию (кроме слогов хангыль), по возрастанию
+    pub codes: &'a [u32],
+    /// начало полной декомпозиции кодпоинта в decompositions, последний элемент - длина decompositions
+    pub offsets: &'a [u16],
+    /// полные канонические декомпозиции
+    pub decompositions: &'a [u32],
+}
+
+/// данные NFD из UnicodeData.txt той же версии Unicode, что и cldr_und (см. unicode_collation_builder nfd)
+pub fn nfd<'a>() -> NormalizationData<'a>
 {
-    include!("./../../data/cldr_und.txt")
+    include!("./../../data/nfd.txt")
 }
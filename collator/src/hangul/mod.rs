@@ -1,5 +1,13 @@
 /// начало блока слогов хангыль
 const HANGUL_S_BASE: u32 = 0xAC00;
+/// количество слогов хангыль
+const HANGUL_S_COUNT: u32 = 11172;
+/// первая начальная согласная (чамо L)
+const HANGUL_L_BASE: u32 = 0x1100;
+/// первая гласная (чамо V)
+const HANGUL_V_BASE: u32 = 0x1161;
+/// кодпоинт, предшествующий первой конечной согласной (чамо T)
+const HANGUL_T_BASE: u32 = 0x11A7;
 /// количество гласных * количество завершающих согласных
 const HANGUL_N_COUNT: u32 = 588;
 /// количество завершающих согласных
@@ -11,7 +19,7 @@ const HANGUL_T_BLOCK_SIZE: u32 = HANGUL_T_COUNT + 1;
     в базовом CLDR веса L1 у чамо хангыль идут последовательно, и соответствуют сортировке T < V < L для
     большинства алгоритмов сопоставления хангыль.
 
-    в текущей реализации L1 располагается в младших 16 битах u32-весов, следовательно, мы можем просто 
+    в текущей реализации L1 располагается в младших 16 битах u32-весов, следовательно, мы можем просто
     вычислить все веса, не полагаясь на данные из таблицы.
 
     тем не менее, текущая реализация - скорее "заглушка", т.к., очевидно, не решает проблему trailing weights.
@@ -41,3 +49,26 @@ pub fn write_hangul_syllable(code: u32, result: &mut Vec<u32>)
         result.push(HANGUL_T_BASE_WEIGHTS + t as u32);
     }
 }
+
+/// кодпоинт - слог хангыль?
+#[inline(always)]
+pub fn is_hangul_syllable(code: u32) -> bool
+{
+    code.wrapping_sub(HANGUL_S_BASE) < HANGUL_S_COUNT
+}
+
+/// каноническая декомпозиция слога хангыль на чамо
+#[inline(always)]
+pub fn decompose_hangul_syllable(code: u32, result: &mut Vec<u32>)
+{
+    let lvt = code.wrapping_sub(HANGUL_S_BASE);
+
+    result.push(HANGUL_L_BASE + lvt / HANGUL_N_COUNT);
+    result.push(HANGUL_V_BASE + (lvt % HANGUL_N_COUNT) / HANGUL_T_BLOCK_SIZE);
+
+    let t = lvt % HANGUL_T_BLOCK_SIZE;
+
+    if t != 0 {
+        result.push(HANGUL_T_BASE + t);
+    }
+}
@@ -0,0 +1,64 @@
+use super::Key;
+use crate::options::Strength;
+
+/// тип границы ключа для поиска по диапазону (аналог UColBoundMode в ICU)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundType
+{
+    /// нижняя граница: ключ, усечённый до заданного уровня
+    Lower,
+    /// верхняя граница для строк, совпадающих на заданном уровне
+    Upper,
+    /// верхняя граница для строк, ключ которых продолжает усечённый ключ
+    UpperLong,
+}
+
+/// вес, добавляемый к верхней границе: больше разделителя уровней, но меньше весов уровня
+/// (за исключением веса 0x0001 U+FFFE, разделителя полей - см. UCA)
+const UPPER_WEIGHT: u16 = 0x0001;
+/// вес, добавляемый к длинной верхней границе (дважды)
+const UPPER_LONG_WEIGHT: u16 = 0xFFFF;
+
+impl Key
+{
+    /// граница для поиска по диапазону ключей: ключ, усечённый до уровня strength
+    ///
+    /// для ключей той же или большей силы:
+    /// - Lower ..= Upper - строки, совпадающие с исходной на уровнях 1 ..= strength;
+    /// - Lower ..= UpperLong - строки, совпадающие на уровнях 1 .. strength, у которых уровень strength
+    ///   начинается с весов исходной строки. для Strength::Primary - строки, начинающиеся с исходной
+    ///   (на первичном уровне)
+    ///
+    /// поиск по префиксу диапазоном ключей возможен только на первичном уровне: при большей силе
+    /// первичные веса должны совпадать полностью, и строки, продолжающие исходную ("mü" - "Müller"),
+    /// в диапазон не попадают. ключи упорядочены по уровням, поэтому такие строки не образуют
+    /// непрерывного диапазона - их выбирают из диапазона Strength::Primary, сравнивая уровни
+    ///
+    /// если в ключе меньше уровней, чем strength - граница строится по имеющимся уровням.
+    /// границы - тоже ключи, их можно сериализовать в любом формате (Key::encode)
+    pub fn bound(&self, strength: Strength, bound_type: BoundType) -> Key
+    {
+        // уровень регистра относится к первичному уровню
+        let levels = strength as usize + self.case_level as usize;
+        let levels = core::cmp::min(levels, self.levels_count());
+
+        let mut weights = Vec::with_capacity(self.weights.len() + 2);
+
+        for level in 0 .. levels {
+            if level != 0 {
+                weights.push(0);
+            }
+
+            weights.extend_from_slice(self.level(level));
+        }
+
+        // добавленные веса относятся к последнему уровню границы
+        match bound_type {
+            BoundType::Lower => (),
+            BoundType::Upper => weights.push(UPPER_WEIGHT),
+            BoundType::UpperLong => weights.extend_from_slice(&[UPPER_LONG_WEIGHT; 2]),
+        }
+
+        Key::from_weights(weights, self.case_level)
+    }
+}
@@ -0,0 +1,159 @@
+use super::Key;
+
+/// общий (наиболее частый) вес вторичного уровня
+pub const COMMON_L2: u16 = 0x0020;
+/// общий (наиболее частый) вес третичного уровня
+pub const COMMON_L3: u16 = 0x0002;
+
+/// разделитель сжатых уровней
+const COMPRESSED_SEPARATOR: u8 = 0x01;
+/// первый байт веса, меньшего, чем общий
+const LOWER_WEIGHT: u8 = 0x02;
+/// первый байт веса, большего, чем общий (к нему добавляются старшие биты веса)
+const HIGHER_WEIGHT: u8 = 0x80;
+/// серии общих весов, за которыми следует конец уровня или меньший вес: LOW ..= LOW + MAX - 1
+const COMMON_LOW: u8 = 0x03;
+/// максимальная длина серии, записываемая одним байтом
+const COMMON_MAX_COUNT: u8 = 0x3E;
+/// полная серия из COMMON_MAX_COUNT общих весов, за которой следует продолжение
+const COMMON_MIDDLE: u8 = COMMON_LOW + COMMON_MAX_COUNT;
+/// серии общих весов, за которыми следует больший вес: HIGH - MAX + 1 ..= HIGH
+const COMMON_HIGH: u8 = COMMON_MIDDLE + COMMON_MAX_COUNT;
+
+/// формат байтового представления ключа
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum KeyFormat
+{
+    /// все уровни - u16 в big-endian (см. Key::to_bytes)
+    #[default]
+    Uncompressed,
+    /// вторичный и третичный уровни сжаты (см. Key::to_compressed_bytes)
+    Compressed,
+}
+
+impl Key
+{
+    /// ключ в виде байт в выбранном формате
+    pub fn encode(&self, format: KeyFormat) -> Vec<u8>
+    {
+        match format {
+            KeyFormat::Uncompressed => self.to_bytes(),
+            KeyFormat::Compressed => self.to_compressed_bytes(),
+        }
+    }
+
+    /// ключ в виде байт со сжатием вторичного и третичного уровней, сравнивается побайтово (memcmp)
+    ///
+    /// формат (стабильный), аналогичен сжатию ключей в ICU:
+    /// - L1 и уровень регистра - как в to_bytes: u16 big-endian, за каждым - разделитель 00 00;
+    /// - L2, L3 - последовательность токенов, после каждого из этих уровней (если далее есть
+    ///   уровни) записывается разделитель 01:
+    ///   - серия из n общих весов (0x0020 для L2, 0x0002 для L3), за которой следует конец уровня
+    ///     или меньший вес: байт 03 + (n - 1);
+    ///   - серия из n общих весов, за которой следует больший вес: байт 7F - (n - 1);
+    ///   - серии длиннее 62 весов записываются байтами 41, каждый из которых заменяет 62 общих
+    ///     веса, остаток - как описано выше;
+    ///   - вес w, меньший общего: 02, w & FF (не встречается в CLDR, оставлено для полноты);
+    ///   - вес w, больший общего: 80 | (w >> 8), w & FF;
+    /// - L4 и идентичный уровень - как в to_bytes: u16 big-endian, разделитель 00 00.
+    ///
+    /// общие веса - минимальные на своих уровнях, поэтому более длинная серия, за которой следует
+    /// больший вес, должна быть меньше более короткой - отсюда убывающие значения байт этих серий
+    pub fn to_compressed_bytes(&self) -> Vec<u8>
+    {
+        let mut result = Vec::with_capacity(self.weights.len() + self.l1_len + 2);
+
+        let case_len = match self.case_level {
+            true => self.level(1).len() + 1,
+            false => 0,
+        };
+
+        let l2_start = self.l1_len + 1 + case_len;
+        let l3_start = l2_start + self.l2_len + 1;
+        let l4_start = l3_start + self.l3_len + 1;
+
+        let len = self.weights.len();
+
+        for weight in self.weights[.. core::cmp::min(l2_start - 1, len)].iter() {
+            result.extend_from_slice(&weight.to_be_bytes());
+        }
+
+        if l2_start > len {
+            return result;
+        }
+
+        result.extend_from_slice(&[0, 0]);
+        compress_level(
+            &self.weights[l2_start .. l2_start + self.l2_len],
+            COMMON_L2,
+            &mut result,
+        );
+
+        if l3_start > len {
+            return result;
+        }
+
+        result.push(COMPRESSED_SEPARATOR);
+        compress_level(
+            &self.weights[l3_start .. l3_start + self.l3_len],
+            COMMON_L3,
+            &mut result,
+        );
+
+        if l4_start > len {
+            return result;
+        }
+
+        result.push(COMPRESSED_SEPARATOR);
+
+        for weight in self.weights[l4_start ..].iter() {
+            result.extend_from_slice(&weight.to_be_bytes());
+        }
+
+        result
+    }
+}
+
+/// записать уровень с run-length сжатием общих весов
+fn compress_level(weights: &[u16], common: u16, result: &mut Vec<u8>)
+{
+    let mut common_count = 0;
+
+    for &weight in weights {
+        if weight == common {
+            common_count += 1;
+            continue;
+        }
+
+        if common_count != 0 {
+            write_commons(common_count, weight > common, result);
+            common_count = 0;
+        }
+
+        match weight > common {
+            true => result.push(HIGHER_WEIGHT | (weight >> 8) as u8),
+            false => result.push(LOWER_WEIGHT),
+        }
+
+        result.push(weight as u8);
+    }
+
+    if common_count != 0 {
+        write_commons(common_count, false, result);
+    }
+}
+
+/// записать серию общих весов
+#[inline(always)]
+fn write_commons(mut count: usize, followed_by_higher: bool, result: &mut Vec<u8>)
+{
+    while count > COMMON_MAX_COUNT as usize {
+        result.push(COMMON_MIDDLE);
+        count -= COMMON_MAX_COUNT as usize;
+    }
+
+    match followed_by_higher {
+        true => result.push(COMMON_HIGH - (count - 1) as u8),
+        false => result.push(COMMON_LOW + (count - 1) as u8),
+    }
+}
@@ -1,5 +1,8 @@
+use super::identical_weights;
+
 /// ключ сопоставления с дополнительной информацией о нём
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Key
 {
     /// u16 веса
@@ -10,4 +13,83 @@ pub struct Key
     pub l2_len: usize,
     /// кол-во весов третичного уровня
     pub l3_len: usize,
+    /// между L1 и L2 записан уровень регистра (caseLevel)
+    pub case_level: bool,
+}
+
+impl Key
+{
+    /// ключ из весов, разделённых по уровням: длины L1 - L3 вычисляются по разделителям
+    pub(crate) fn from_weights(weights: Vec<u16>, case_level: bool) -> Self
+    {
+        let mut levels = weights
+            .split(|&weight| weight == 0)
+            .map(|level| level.len());
+
+        let l1_len = levels.next().unwrap_or(0);
+
+        if case_level {
+            levels.next();
+        }
+
+        let l2_len = levels.next().unwrap_or(0);
+        let l3_len = levels.next().unwrap_or(0);
+
+        Self {
+            weights,
+            l1_len,
+            l2_len,
+            l3_len,
+            case_level,
+        }
+    }
+
+    /// количество уровней в ключе
+    pub fn levels_count(&self) -> usize
+    {
+        self.weights.iter().filter(|&&weight| weight == 0).count() + 1
+    }
+
+    /// дописать идентичный уровень - кодпоинты NFD-формы строки
+    pub(crate) fn push_identical_level(&mut self, codes: &[u32])
+    {
+        self.weights.reserve(codes.len() * 2 + 1);
+        self.weights.push(0);
+
+        for &code in codes {
+            self.weights.extend_from_slice(&identical_weights(code));
+        }
+    }
+
+    /// веса уровня (0 - L1), пустой слайс - уровень отсутствует или не содержит весов
+    pub fn level(&self, level: usize) -> &[u16]
+    {
+        let mut levels = self.weights.split(|&weight| weight == 0);
+
+        levels.nth(level).unwrap_or(&[])
+    }
+
+    /// ключ в виде последовательности байт, сравниваемой побайтово (memcmp)
+    ///
+    /// формат (стабильный):
+    /// - каждый u16-вес ключа записывается двумя байтами в порядке big-endian;
+    /// - веса внутри уровня всегда ненулевые, уровни разделены весом 0x0000 (байты 00 00);
+    /// - уровни идут в порядке L1, уровень регистра, L2, L3, L4, идентичный уровень; отсутствующие
+    ///   уровни не записываются, кроме пустого L4 в режиме Non Ignorable при наличии идентичного
+    ///   уровня;
+    /// - идентичный уровень: каждый кодпоинт NFD-формы строки записывается двумя u16:
+    ///   (кодпоинт >> 15) + 1 и (кодпоинт & 7FFF) | 8000.
+    ///
+    /// все значения занимают ровно 2 байта, поэтому первое различие побайтового сравнения
+    /// приходится на одну и ту же пару весов - результат совпадает с compare_keys
+    pub fn to_bytes(&self) -> Vec<u8>
+    {
+        let mut result = Vec::with_capacity(self.weights.len() * 2);
+
+        for weight in self.weights.iter() {
+            result.extend_from_slice(&weight.to_be_bytes());
+        }
+
+        result
+    }
 }
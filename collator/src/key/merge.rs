@@ -0,0 +1,37 @@
+use super::Key;
+
+/// разделитель полей в объединённом ключе: меньше любого веса уровня, совпадает с первичным
+/// весом U+FFFE (разделитель полей в UCA)
+pub const MERGE_SEPARATOR: u16 = 0x0001;
+
+/// объединить ключи полей записи в один ключ (аналог mergeSortKeys в ICU)
+///
+/// уровни объединяются по отдельности: L1 первого поля, разделитель, L1 второго поля, ...,
+/// разделитель уровней, L2 первого поля и т.д. сравнение объединённых ключей равносильно
+/// последовательному сравнению полей на каждом из уровней, начиная с первичного.
+///
+/// если ключи содержат разное количество уровней - недостающие уровни считаются пустыми
+pub fn merge_keys(keys: &[Key]) -> Key
+{
+    let levels = keys.iter().map(|key| key.levels_count()).max().unwrap_or(0);
+
+    let mut weights = Vec::with_capacity(keys.iter().map(|key| key.weights.len() + 4).sum());
+
+    for level in 0 .. levels {
+        if level != 0 {
+            weights.push(0);
+        }
+
+        for (i, key) in keys.iter().enumerate() {
+            if i != 0 {
+                weights.push(MERGE_SEPARATOR);
+            }
+
+            weights.extend_from_slice(key.level(level));
+        }
+    }
+
+    let case_level = keys.iter().any(|key| key.case_level);
+
+    Key::from_weights(weights, case_level)
+}
@@ -1,14 +1,30 @@
 use core::cmp::Ordering;
 
-use crate::{options::*, weights::Weights};
+use crate::options::*;
+use crate::reorder::{Reordering, CLDR_UND_GROUPS};
+use crate::weights::{is_uppercase_l3, Weights};
 
+mod bound;
+mod compressed;
+#[allow(clippy::module_inception)]
 mod key;
+mod merge;
+mod partial;
 
+pub use bound::BoundType;
+pub use compressed::KeyFormat;
 pub use key::Key;
+pub use merge::{merge_keys, MERGE_SEPARATOR};
+pub use partial::SortKeyParts;
+
+/// вес уровня регистра, сортируемый первым (строчные или, при caseFirst = Upper, прописные)
+const CASE_FIRST_WEIGHT: u16 = 0x0002;
+/// вес уровня регистра, сортируемый вторым
+const CASE_SECOND_WEIGHT: u16 = 0x0003;
 
 /// сравнение ключей
 #[inline]
-pub fn compare_keys(a: &Vec<u16>, b: &Vec<u16>) -> Ordering
+pub fn compare_keys(a: &[u16], b: &[u16]) -> Ordering
 {
     if a == b {
         return Ordering::Equal;
@@ -23,89 +39,312 @@ pub fn compare_keys(a: &Vec<u16>, b: &Vec<u16>) -> Ordering
     }
 }
 
-/// создать ключ из u32-весов
+/// создать ключ из u32-весов таблиц CLDR (root)
 #[inline]
-pub fn compose_key(u32_weights: &Vec<u32>, options: CollatorOptions) -> Key
+pub fn compose_key(u32_weights: &[u32], options: &CollatorOptions) -> Key
 {
-    let weights: &Vec<Weights> = unsafe { core::mem::transmute(u32_weights) };
+    // для таблиц CLDR известны границы всех групп - поддерживаются любые опции
+    match Reordering::new(options, Some(&CLDR_UND_GROUPS)) {
+        Ok(reordering) => compose_reordered_key(u32_weights, options, &reordering),
+        Err(_) => unreachable!(),
+    }
+}
 
-    let result = match options.alternate {
-        AlternateHandling::NonIgnorable => compose_non_ignorable_key(weights, options.strength),
-        AlternateHandling::Shifted => compose_shifted_key(weights, options.strength),
-    };
+/// создать ключ из u32-весов с заранее подготовленной перестановкой групп
+#[inline]
+pub(crate) fn compose_reordered_key(
+    u32_weights: &[u32],
+    options: &CollatorOptions,
+    reordering: &Reordering,
+) -> Key
+{
+    let weights = Weights::from_slice(u32_weights);
 
-    result
+    match options.alternate {
+        AlternateHandling::NonIgnorable => compose_non_ignorable_key(weights, options, reordering),
+        AlternateHandling::Shifted => compose_shifted_key(weights, options, reordering),
+        AlternateHandling::Blanked => compose_blanked_key(weights, options, reordering),
+        AlternateHandling::ShiftTrimmed => compose_shift_trimmed_key(weights, options, reordering),
+    }
 }
 
-/// Non Ignorable
+/// количество уровней ключа, получаемых из весов (без идентичного уровня)
 #[inline]
-fn compose_non_ignorable_key(weights: &Vec<Weights>, strength: Strength) -> Key
+pub(crate) fn levels_count(options: &CollatorOptions) -> u8
 {
-    let mut primary = vec![];
-    let mut secondary = vec![];
-    let mut tetriary = vec![];
+    weights_levels(options) + options.case_level as u8
+}
 
-    macro_rules! push {
-        ($to: ident, $entry: expr) => {
-            let value = $entry;
+/// индекс веса в результате entry_weights для уровня ключа с номером level
+#[inline(always)]
+pub(crate) fn entry_level(level: usize, options: &CollatorOptions) -> usize
+{
+    match level == 0 || options.case_level {
+        true => level,
+        false => level + 1,
+    }
+}
 
-            if value != 0 {
-                $to.push(value);
-            }
-        };
+/// веса уровня (индекс в результате entry_weights) записываются в обратном порядке
+#[inline(always)]
+pub(crate) fn is_backwards_level(level: usize, options: &CollatorOptions) -> bool
+{
+    level == 2 && options.backwards_secondary
+}
+
+/// конечные веса FFFF уровня (индекс в результате entry_weights) отбрасываются
+#[inline(always)]
+pub(crate) fn is_trimmed_level(level: usize, options: &CollatorOptions) -> bool
+{
+    level == 4 && options.alternate == AlternateHandling::ShiftTrimmed
+}
+
+/// веса уровня (индекс в результате entry_weights) нельзя сравнивать по мере вычисления -
+/// уровень собирается целиком и приводится к виду, записываемому в ключ (arrange_level)
+#[inline(always)]
+pub(crate) fn is_collected_level(level: usize, options: &CollatorOptions) -> bool
+{
+    is_backwards_level(level, options) || is_trimmed_level(level, options)
+}
+
+/// привести собранные веса уровня к виду, записываемому в ключ
+#[inline(always)]
+pub(crate) fn arrange_level(weights: &mut Vec<u16>, level: usize, options: &CollatorOptions)
+{
+    if is_backwards_level(level, options) {
+        weights.reverse();
     }
 
-    match strength {
-        Strength::Primary => {
-            for entry in weights {
-                push!(primary, entry.l1());
-            }
-        }
-        Strength::Secondary => {
-            for entry in weights {
-                push!(primary, entry.l1());
-                push!(secondary, entry.l2());
-            }
+    if is_trimmed_level(level, options) {
+        trim_quaternary(weights);
+    }
+}
+
+/// количество уровней L1 - L4, получаемых из весов
+#[inline(always)]
+fn weights_levels(options: &CollatorOptions) -> u8
+{
+    match options.alternate {
+        AlternateHandling::NonIgnorable | AlternateHandling::Blanked => {
+            non_ignorable_levels(options.strength)
         }
-        _ => {
-            for entry in weights {
-                push!(primary, entry.l1());
-                push!(secondary, entry.l2());
-                push!(tetriary, entry.l3());
-            }
+        AlternateHandling::Shifted | AlternateHandling::ShiftTrimmed => {
+            shifted_levels(options.strength)
         }
     }
+}
 
-    macro_rules! append {
-        ($($level: expr, $from:ident),+) => {
-            $(
-                if strength as u8 >= $level {
-                    primary.push(0);
-                    primary.append(&mut $from);
-                }
-            )+
-        }
+/// вес идентичного уровня для кодпоинта: пара ненулевых u16, порядок совпадает с порядком кодпоинтов
+#[inline(always)]
+pub(crate) fn identical_weights(code: u32) -> [u16; 2]
+{
+    [((code >> 15) + 1) as u16, (code as u16 & 0x7FFF) | 0x8000]
+}
+
+/// веса элемента по уровням L1, регистр, L2, L3, L4, 0 - вес на уровне отсутствует
+///
+/// состояние following_a_variable передаётся между вызовами для последовательных элементов
+#[inline(always)]
+pub(crate) fn entry_weights(
+    entry: &Weights,
+    options: &CollatorOptions,
+    reordering: &Reordering,
+    following_a_variable: &mut bool,
+) -> [u16; 5]
+{
+    let weights = match options.alternate {
+        AlternateHandling::NonIgnorable => non_ignorable_weights(entry),
+        AlternateHandling::Shifted | AlternateHandling::ShiftTrimmed => shifted_weights(
+            entry,
+            is_variable(entry, options.max_variable, reordering),
+            options,
+            following_a_variable,
+        ),
+        AlternateHandling::Blanked => blanked_weights(
+            entry,
+            is_variable(entry, options.max_variable, reordering),
+            following_a_variable,
+        ),
+    };
+
+    tailor_weights(weights, options, reordering)
+}
+
+/// изменение весов элемента, заданное опциями (reorder, caseFirst, caseLevel)
+#[inline(always)]
+fn tailor_weights(weights: [u16; 4], options: &CollatorOptions, reordering: &Reordering)
+    -> [u16; 5]
+{
+    let [l1, l2, l3, l4] = weights;
+
+    let case = match options.case_level && l1 != 0 {
+        true => case_weight(l3, options.case_first),
+        false => 0,
+    };
+
+    // второй вес вычисляемых весов и весов числа (L2 = 0) - продолжение первого, не переставляется
+    let l1 = match l2 != 0 {
+        true => reordering.remap(l1),
+        false => l1,
+    };
+
+    [
+        l1,
+        case,
+        l2,
+        case_first_l3(l3, options.case_first),
+        reordering.remap(l4),
+    ]
+}
+
+/// caseLevel: вес уровня регистра, записывается только для элементов с первичным весом
+#[inline(always)]
+fn case_weight(l3: u16, case_first: CaseFirst) -> u16
+{
+    match is_uppercase_l3(l3) == (case_first == CaseFirst::Upper) {
+        true => CASE_FIRST_WEIGHT,
+        false => CASE_SECOND_WEIGHT,
     }
+}
 
-    let l1_len = primary.len();
-    let l2_len = secondary.len();
-    let l3_len = tetriary.len();
+/// caseFirst: третичный вес, в котором регистр важнее остальных третичных различий
+///
+/// к весу добавляется старший бит: в режиме Upper он установлен у строчных, в режиме Lower - у
+/// прописных. таким образом, сначала идут все варианты одного регистра, затем - другого
+#[inline(always)]
+fn case_first_l3(l3: u16, case_first: CaseFirst) -> u16
+{
+    if l3 == 0 {
+        return 0;
+    }
 
-    append!(2, secondary, 3, tetriary);
+    let is_upper = is_uppercase_l3(l3);
 
-    Key {
-        weights: primary,
-        l1_len,
-        l2_len,
-        l3_len,
+    match case_first {
+        CaseFirst::Off => l3,
+        CaseFirst::Upper => l3 | ((!is_upper as u16) << 5),
+        CaseFirst::Lower => l3 | ((is_upper as u16) << 5),
     }
 }
 
+/// Non Ignorable
+#[inline]
+fn compose_non_ignorable_key(
+    weights: &[Weights],
+    options: &CollatorOptions,
+    reordering: &Reordering,
+) -> Key
+{
+    compose_levels(weights, options, |entry| {
+        tailor_weights(non_ignorable_weights(entry), options, reordering)
+    })
+}
+
 /// Shifted
 #[inline]
-fn compose_shifted_key(weights: &Vec<Weights>, strength: Strength) -> Key
+fn compose_shifted_key(
+    weights: &[Weights],
+    options: &CollatorOptions,
+    reordering: &Reordering,
+) -> Key
+{
+    let mut following_a_variable = false;
+
+    compose_levels(weights, options, |entry| {
+        tailor_weights(
+            shifted_weights(
+                entry,
+                is_variable(entry, options.max_variable, reordering),
+                options,
+                &mut following_a_variable,
+            ),
+            options,
+            reordering,
+        )
+    })
+}
+
+/// Blanked
+#[inline]
+fn compose_blanked_key(
+    weights: &[Weights],
+    options: &CollatorOptions,
+    reordering: &Reordering,
+) -> Key
+{
+    let mut following_a_variable = false;
+
+    compose_levels(weights, options, |entry| {
+        tailor_weights(
+            blanked_weights(
+                entry,
+                is_variable(entry, options.max_variable, reordering),
+                &mut following_a_variable,
+            ),
+            options,
+            reordering,
+        )
+    })
+}
+
+/// Shift-Trimmed
+#[inline]
+fn compose_shift_trimmed_key(
+    weights: &[Weights],
+    options: &CollatorOptions,
+    reordering: &Reordering,
+) -> Key
 {
+    let mut key = compose_shifted_key(weights, options, reordering);
+
+    // L4 - последний уровень ключа, идентичный уровень добавляется позже
+    if weights_levels(options) >= 4 {
+        trim_quaternary(&mut key.weights);
+    }
+
+    key
+}
+
+/// Shift-Trimmed: отбросить конечные веса FFFF четвертичного уровня
+#[inline(always)]
+fn trim_quaternary(weights: &mut Vec<u16>)
+{
+    while weights.last() == Some(&0xFFFF) {
+        weights.pop();
+    }
+}
+
+/// Non Ignorable: количество уровней из весов
+///
+/// L4 в этом режиме не используется, но для идентичного уровня записывается пустой L4 -
+/// чтобы идентичный уровень всегда следовал за L4
+#[inline(always)]
+fn non_ignorable_levels(strength: Strength) -> u8
+{
+    match strength {
+        Strength::Quaternary => 3,
+        Strength::Identical => 4,
+        _ => strength as u8,
+    }
+}
+
+/// Shifted: количество уровней из весов
+#[inline(always)]
+fn shifted_levels(strength: Strength) -> u8
+{
+    core::cmp::min(strength as u8, 4)
+}
+
+/// собрать ключ из весов элементов, разложенных по уровням
+#[inline(always)]
+fn compose_levels<F>(weights: &[Weights], options: &CollatorOptions, mut entry_weights: F) -> Key
+where
+    F: FnMut(&Weights) -> [u16; 5],
+{
+    let levels = weights_levels(options);
+
     let mut primary = vec![];
+    let mut case = vec![];
     let mut secondary = vec![];
     let mut tetriary = vec![];
     let mut quaternary = vec![];
@@ -118,128 +357,32 @@ fn compose_shifted_key(weights: &Vec<Weights>, strength: Strength) -> Key
         };
     }
 
-    let mut following_a_variable = false;
+    for entry in weights {
+        let [l1, lc, l2, l3, l4] = entry_weights(entry);
 
-    match strength {
-        Strength::Primary => {
-            for entry in weights {
-                if !entry.is_variable() {
-                    let l1 = entry.l1();
+        push!(primary, l1);
 
-                    push!(primary, l1);
-                }
-            }
+        if options.case_level {
+            push!(case, lc);
         }
-        Strength::Secondary => {
-            for entry in weights {
-                if entry.is_variable() {
-                    following_a_variable = true;
-                    continue;
-                }
-
-                let l1 = entry.l1();
-
-                if following_a_variable && l1 == 0 {
-                    continue;
-                }
-
-                let l2 = entry.l2();
-
-                push!(primary, l1);
-                push!(secondary, l2);
 
-                following_a_variable = false;
-            }
+        if levels >= 2 {
+            push!(secondary, l2);
         }
-        Strength::Tetriary => {
-            for entry in weights {
-                if entry.is_variable() {
-                    following_a_variable = true;
-                    continue;
-                }
-
-                let l1 = entry.l1();
-
-                if following_a_variable && l1 == 0 {
-                    continue;
-                }
-
-                let l2 = entry.l2();
-                let l3 = entry.l3();
 
-                push!(primary, l1);
-                push!(secondary, l2);
-                push!(tetriary, l3);
-
-                following_a_variable = false;
-            }
+        if levels >= 3 {
+            push!(tetriary, l3);
         }
-        Strength::Quaternary => {
-            for entry in weights {
-                // правила из TR #10: (https://www.unicode.org/reports/tr10/tr10-49.html#Variable_Weighting)
-                //
-                // L1, L2, L3 = 0                               -> [.0000.0000.0000.0000]
-                // L1 = 0, L3 ≠ 0,   following a Variable       -> [.0000.0000.0000.0000] combining grave
-                // L1 ≠ 0,           Variable	                -> old L1 [.0000.0000.0000.0209] space
-                // L1 = 0, L3 ≠ 0,   not following a Variable   -> FFFF [.0000.0035.0002.FFFF] combining grave
-                // L1 ≠ 0,           not Variable               -> FFFF [.06D9.0020.0008.FFFF] Capital A
-
-                // игнорируемый вес
-                if entry.value() == 0 {
-                    continue;
-                }
-
-                let l1 = entry.l1();
-                let l3 = entry.l3();
-
-                // L1 = 0, L3 ≠ 0
-                if (l1 == 0) && (l3 != 0) {
-                    match following_a_variable {
-                        true => continue,
-                        false => {
-                            push!(quaternary, 0xFFFF)
-                        }
-                    }
-                }
-
-                let is_variable = entry.is_variable();
-
-                // L1 ≠ 0
-                if l1 != 0 {
-                    match is_variable {
-                        true => {
-                            following_a_variable = true;
-
-                            push!(quaternary, l1);
-                            continue;
-                        }
-                        false => {
-                            // непонятно, насколько это корректно.
-                            // U+FFFE - в правилах: FFFF. в тестах CLDR: 0001
-                            if l1 == 1 {
-                                push!(quaternary, 0x0001);
-                            } else
-                            // эта проверка - не соответствует официальной документации, но
-                            // соответствует результатам, получаемым в тестах.
-                            // пытаюсь уточнить :(
-                            if l3 != 0 {
-                                push!(quaternary, 0xFFFF)
-                            }
-                        }
-                    }
-                }
-
-                let l2 = entry.l2();
 
-                push!(primary, l1);
-                push!(secondary, l2);
-                push!(tetriary, l3);
-
-                following_a_variable = is_variable;
-            }
+        if levels >= 4 {
+            push!(quaternary, l4);
         }
     }
 
+    if options.backwards_secondary {
+        secondary.reverse();
+    }
+
     let l1_len = primary.len();
     let l2_len = secondary.len();
     let l3_len = tetriary.len();
@@ -247,7 +390,7 @@ fn compose_shifted_key(weights: &Vec<Weights>, strength: Strength) -> Key
     macro_rules! append {
         ($($level: expr, $from:ident),+) => {
             $(
-                if strength as u8 >= $level {
+                if levels >= $level {
                     primary.push(0);
                     primary.append(&mut $from);
                 }
@@ -255,6 +398,11 @@ fn compose_shifted_key(weights: &Vec<Weights>, strength: Strength) -> Key
         }
     }
 
+    if options.case_level {
+        primary.push(0);
+        primary.append(&mut case);
+    }
+
     append!(2, secondary, 3, tetriary, 4, quaternary);
 
     Key {
@@ -262,5 +410,129 @@ fn compose_shifted_key(weights: &Vec<Weights>, strength: Strength) -> Key
         l1_len,
         l2_len,
         l3_len,
+        case_level: options.case_level,
+    }
+}
+
+/// переменный вес с учётом maxVariable: при maxVariable = punct - флаг веса, иначе - первичный вес
+/// в группах от пробелов до maxVariable (границы групп - из таблиц, см. Reordering)
+#[inline(always)]
+fn is_variable(entry: &Weights, max_variable: MaxVariable, reordering: &Reordering) -> bool
+{
+    match (reordering.variable_primaries(), entry.l1()) {
+        (None, _) => entry.is_variable(),
+        // переменные веса без первичного веса встречаются только у знаков пунктуации (кавычки)
+        (Some(_), 0) => entry.is_variable() && max_variable != MaxVariable::Space,
+        (Some(primaries), l1) => primaries.contains(&l1),
+    }
+}
+
+/// Shifted: четвертичный вес непеременного элемента с L1 ≠ 0
+///
+/// U+FFFE - в правилах: FFFF, в тестах CLDR: 0001. в тестах CLDR вес FFFF не записывается и для
+/// элементов без третичного веса (второй элемент вычисляемых весов)
+#[inline(always)]
+fn quaternary_weight(l1: u16, l3: u16, mode: QuaternaryMode) -> u16
+{
+    match mode {
+        QuaternaryMode::Uca => 0xFFFF,
+        QuaternaryMode::Cldr => match (l1, l3) {
+            (1, _) => 0x0001,
+            (_, 0) => 0,
+            _ => 0xFFFF,
+        },
+    }
+}
+
+/// Non Ignorable: веса элемента по уровням
+#[inline(always)]
+fn non_ignorable_weights(entry: &Weights) -> [u16; 4]
+{
+    [entry.l1(), entry.l2(), entry.l3(), 0]
+}
+
+/// Blanked: веса элемента по уровням. переменные веса и следующие за ними игнорируемые
+/// полностью игнорируются
+#[inline(always)]
+fn blanked_weights(entry: &Weights, is_variable: bool, following_a_variable: &mut bool)
+    -> [u16; 4]
+{
+    if is_variable {
+        *following_a_variable = true;
+        return [0; 4];
+    }
+
+    let l1 = entry.l1();
+
+    if *following_a_variable && l1 == 0 {
+        return [0; 4];
+    }
+
+    *following_a_variable = false;
+
+    [l1, entry.l2(), entry.l3(), 0]
+}
+
+/// Shifted: веса элемента по уровням
+#[inline(always)]
+fn shifted_weights(
+    entry: &Weights,
+    is_variable: bool,
+    options: &CollatorOptions,
+    following_a_variable: &mut bool,
+) -> [u16; 4]
+{
+    match options.strength {
+        // L2, L3 в ключ не попадают, но L3 нужен для уровня регистра
+        Strength::Primary => match is_variable {
+            true => [0; 4],
+            false => non_ignorable_weights(entry),
+        },
+        Strength::Secondary | Strength::Tetriary => {
+            blanked_weights(entry, is_variable, following_a_variable)
+        }
+        Strength::Quaternary | Strength::Identical => {
+            // правила из TR #10: (https://www.unicode.org/reports/tr10/tr10-49.html#Variable_Weighting)
+            //
+            // L1, L2, L3 = 0                               -> [.0000.0000.0000.0000]
+            // L1 = 0, L3 ≠ 0,   following a Variable       -> [.0000.0000.0000.0000] combining grave
+            // L1 ≠ 0,           Variable	                -> old L1 [.0000.0000.0000.0209] space
+            // L1 = 0, L3 ≠ 0,   not following a Variable   -> FFFF [.0000.0035.0002.FFFF] combining grave
+            // L1 ≠ 0,           not Variable               -> FFFF [.06D9.0020.0008.FFFF] Capital A
+
+            // игнорируемый вес
+            if entry.value() == 0 {
+                return [0; 4];
+            }
+
+            let l1 = entry.l1();
+            let l3 = entry.l3();
+
+            let mut l4 = 0;
+
+            // L1 = 0, L3 ≠ 0
+            if (l1 == 0) && (l3 != 0) {
+                match *following_a_variable {
+                    true => return [0; 4],
+                    false => l4 = 0xFFFF,
+                }
+            }
+
+            // L1 ≠ 0
+            if l1 != 0 {
+                match is_variable {
+                    true => {
+                        *following_a_variable = true;
+
+                        return [0, 0, 0, l1];
+                    }
+                    false => l4 = quaternary_weight(l1, l3, options.quaternary_mode),
+                }
+            }
+
+            *following_a_variable = is_variable;
+
+            [l1, entry.l2(), l3, l4]
+        }
     }
 }
@@ -0,0 +1,232 @@
+use super::{
+    arrange_level, entry_level, entry_weights, identical_weights, is_collected_level, levels_count,
+};
+use crate::nfd::nfd;
+use crate::options::Strength;
+use crate::stream::WeightsStream;
+use crate::weights::Weights;
+use crate::Collator;
+
+/// размер порции весов, получаемой из строки за один проход цикла
+const WEIGHTS_PORTION: usize = 32;
+
+/// построение байтового ключа (формат Key::to_bytes) частями заданного размера, аналог
+/// nextSortKeyPart в ICU
+///
+/// строка обрабатывается заново для каждого уровня, веса следующего уровня вычисляются только
+/// тогда, когда запрошены байты после окончания предыдущего
+pub struct SortKeyParts<'a>
+{
+    /// коллатор
+    collator: &'a Collator<'a>,
+    /// исходная строка
+    input: &'a str,
+    /// состояние получения весов строки
+    stream: WeightsStream<'a>,
+    /// текущая порция весов
+    weights: Vec<u32>,
+    /// позиция в текущей порции
+    index: usize,
+    /// текущий уровень (0 - L1)
+    level: u8,
+    /// количество уровней ключа
+    levels: u8,
+    /// номер идентичного уровня (u8::MAX - уровень отсутствует)
+    identical_level: u8,
+    /// веса текущего уровня вычисляются целиком при переходе к нему
+    computed_level: bool,
+    /// веса текущего уровня, если он вычисляется целиком при переходе к нему - идентичный уровень,
+    /// уровень с обратным порядком весов или с отбрасываемыми конечными весами
+    level_weights: Vec<u16>,
+    /// состояние обработки переменных весов
+    following_a_variable: bool,
+    /// младший байт веса, не поместившийся в предыдущую часть
+    pending: Option<u8>,
+}
+
+impl<'a> SortKeyParts<'a>
+{
+    /// новое состояние для строки
+    pub(crate) fn new(collator: &'a Collator<'a>, input: &'a str) -> Self
+    {
+        let options = &collator.options;
+        let levels = levels_count(options);
+
+        let (levels, identical_level) = match options.strength {
+            Strength::Identical => (levels + 1, levels),
+            _ => (levels, u8::MAX),
+        };
+
+        Self {
+            collator,
+            input,
+            stream: collator.weights_stream(input),
+            weights: Vec::with_capacity(WEIGHTS_PORTION),
+            index: 0,
+            level: 0,
+            levels,
+            identical_level,
+            computed_level: false,
+            level_weights: vec![],
+            following_a_variable: false,
+            pending: None,
+        }
+    }
+
+    /// записать в dest следующую часть ключа, возвращает количество записанных байт.
+    /// если оно меньше длины dest - ключ закончился
+    pub fn next_part(&mut self, dest: &mut [u8]) -> usize
+    {
+        let mut written = 0;
+
+        while written < dest.len() {
+            if let Some(byte) = self.pending.take() {
+                dest[written] = byte;
+                written += 1;
+
+                continue;
+            }
+
+            let [high, low] = match self.next_weight() {
+                Some(weight) => weight.to_be_bytes(),
+                None => break,
+            };
+
+            dest[written] = high;
+            written += 1;
+
+            match written < dest.len() {
+                true => {
+                    dest[written] = low;
+                    written += 1;
+                }
+                false => self.pending = Some(low),
+            }
+        }
+
+        written
+    }
+
+    /// ключ построен полностью
+    pub fn is_finished(&self) -> bool
+    {
+        self.level >= self.levels && self.pending.is_none()
+    }
+
+    /// следующий вес ключа, 0 - разделитель уровней
+    #[inline(always)]
+    fn next_weight(&mut self) -> Option<u16>
+    {
+        while self.level < self.levels {
+            if self.computed_level {
+                match self.level_weights.get(self.index) {
+                    Some(&weight) => {
+                        self.index += 1;
+                        return Some(weight);
+                    }
+                    None => return self.next_level(),
+                }
+            }
+
+            match self.next_entry() {
+                Some(entry) => {
+                    let options = &self.collator.options;
+                    let weight = entry_weights(
+                        &entry,
+                        options,
+                        &self.collator.reordering,
+                        &mut self.following_a_variable,
+                    )[entry_level(self.level as usize, options)];
+
+                    if weight != 0 {
+                        return Some(weight);
+                    }
+                }
+                None => return self.next_level(),
+            }
+        }
+
+        None
+    }
+
+    /// переход к следующему уровню: разделитель уровней или None, если уровней больше нет
+    #[inline(always)]
+    fn next_level(&mut self) -> Option<u16>
+    {
+        self.level += 1;
+
+        if self.level >= self.levels {
+            return None;
+        }
+
+        // следующий уровень - обрабатываем строку с начала
+        self.stream = self.collator.weights_stream(self.input);
+        self.weights.clear();
+        self.index = 0;
+        self.following_a_variable = false;
+
+        let level = entry_level(self.level as usize, &self.collator.options);
+
+        self.computed_level = true;
+
+        // идентичный уровень - кодпоинты NFD-формы строки
+        if self.level == self.identical_level {
+            self.level_weights = nfd(self.input)
+                .into_iter()
+                .flat_map(identical_weights)
+                .collect();
+        } else if is_collected_level(level, &self.collator.options) {
+            self.level_weights = self.collected_weights(level);
+        } else {
+            self.computed_level = false;
+        }
+
+        Some(0)
+    }
+
+    /// веса уровня в том виде, в котором они записываются в ключ, после вычисления текущий проход
+    /// по строке закончен, индекс указывает на начало результата
+    fn collected_weights(&mut self, level: usize) -> Vec<u16>
+    {
+        let mut result = vec![];
+
+        while let Some(entry) = self.next_entry() {
+            let weight = entry_weights(
+                &entry,
+                &self.collator.options,
+                &self.collator.reordering,
+                &mut self.following_a_variable,
+            )[level];
+
+            if weight != 0 {
+                result.push(weight);
+            }
+        }
+
+        arrange_level(&mut result, level, &self.collator.options);
+        self.index = 0;
+
+        result
+    }
+
+    /// следующий элемент весов текущего прохода по строке
+    #[inline(always)]
+    fn next_entry(&mut self) -> Option<Weights>
+    {
+        while self.index >= self.weights.len() {
+            if self.stream.is_finished {
+                return None;
+            }
+
+            self.weights.clear();
+            self.index = 0;
+
+            self.collator
+                .weights_loop(&mut self.stream, &mut self.weights, WEIGHTS_PORTION);
+        }
+
+        self.index += 1;
+
+        Some(Weights::from(self.weights[self.index - 1]))
+    }
+}
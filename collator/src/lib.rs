@@ -1,23 +1,41 @@
+use core::ops::Deref;
+use std::sync::Arc;
+
 use ce::{CollationElement, CollationElementValue};
 use codepoint::{CodepointWithData, CodepointsIter};
-use data::WeightsData;
+use container::ContainerError;
 use hangul::write_hangul_syllable;
-use implicit::implicit_weights;
-use key::{compose_key, Key};
-use options::CollatorOptions;
+use key::{compose_reordered_key, Key, KeyFormat, SortKeyParts};
+use nfd::nfd;
+use numeric::write_numeric_weights;
+use options::{CollatorOptions, OptionsError, Strength};
+use reorder::{PrimaryGroups, Reordering, CLDR_UND_GROUPS};
 use slice::aligned::Aligned;
+use stream::WeightsStream;
 use trie::{TrieIter, TrieNode};
 
+pub mod container;
 pub mod options;
 
+pub use data::{cldr_und, WeightsData};
+pub use implicit::implicit_weights;
+pub use nfd::is_fcd;
+pub use validate::DataError;
+
 mod ce;
 mod codepoint;
+mod compare;
 mod data;
 mod hangul;
 mod implicit;
 pub mod key;
+mod nfd;
+mod numeric;
+mod reorder;
 mod slice;
+mod stream;
 mod trie;
+mod validate;
 pub mod weights;
 
 /// веса считаются алгоритмически
@@ -40,9 +58,9 @@ pub const MARKER_NONSTARTER_TRIE: u8 = 0b_110;
 /// частный случай декомпозиции - кодпоинт - слог хангыль
 pub const MARKER_CCC_HANGUL: u8 = 0xFF;
 
-/// коллатор
+/// таблицы весов - общие для коллаторов с разными опциями (см. Collator::with_options)
 #[repr(C, align(16))]
-pub struct Collator<'a>
+pub struct CollatorData<'a>
 {
     /// расширения
     expansions: Aligned<'a, u32>,
@@ -56,52 +74,367 @@ pub struct Collator<'a>
     index: Aligned<'a, u16>,
     /// с U+0000 и до этого кодпоинта включительно блоки в data идут последовательно
     continuous_block_end: u32,
+    /// границы групп первичных весов (maxVariable, reorder), None - неизвестны
+    groups: Option<&'a PrimaryGroups>,
+}
+
+/// таблицы весов CLDR (root), выровненные при компиляции - без копирования и аллокаций
+static CLDR_UND: CollatorData<'static> = CollatorData::new();
+
+/// таблицы весов коллатора: статические или общие для нескольких коллаторов
+#[derive(Clone)]
+enum DataRef<'a>
+{
+    Static(&'a CollatorData<'a>),
+    Shared(Arc<CollatorData<'a>>),
+}
+
+impl<'a> Deref for DataRef<'a>
+{
+    type Target = CollatorData<'a>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target
+    {
+        match self {
+            Self::Static(data) => data,
+            Self::Shared(data) => data,
+        }
+    }
+}
+
+/// коллатор: общие таблицы весов и опции
+#[derive(Clone)]
+pub struct Collator<'a>
+{
+    /// таблицы весов
+    data: DataRef<'a>,
     /// опции
     options: CollatorOptions,
+    /// перестановка групп первичных весов, заданная опцией reorder
+    reordering: Reordering,
+    /// первичный вес цифры 0 в таблицах (numeric), 0 - числовое сопоставление не используется
+    numeric_primary: u16,
 }
 
 impl<'a> Collator<'a>
 {
     /// создать ключ сопоставления
-    /// TODO: добавить опции
     #[inline(never)]
     pub fn get_key(&self, input: &str) -> Key
     {
         let result = self.get_weights(input);
 
         // сформируем ключ
-        compose_key(&result, self.options)
+        let mut key = compose_reordered_key(&result, &self.options, &self.reordering);
+
+        if self.options.strength == Strength::Identical {
+            key.push_identical_level(&nfd(input));
+        }
+
+        key
+    }
+
+    /// ключ сопоставления в виде байт, порядок которых совпадает с порядком ключей
+    /// (см. Key::to_bytes, Key::to_compressed_bytes)
+    pub fn get_sort_key_bytes(&self, input: &str, format: KeyFormat) -> Vec<u8>
+    {
+        self.get_key(input).encode(format)
+    }
+
+    /// построение байтового ключа частями (см. SortKeyParts::next_part)
+    pub fn sort_key_parts<'s>(&'s self, input: &'s str) -> SortKeyParts<'s>
+    {
+        SortKeyParts::new(self, input)
+    }
+
+    /// ключ сопоставления с другими опциями - без создания коллатора
+    ///
+    /// порядок групп (reorder) подготавливается при каждом вызове, при частом использовании
+    /// одних и тех же опций с reorder лучше создать коллатор with_options
+    pub fn get_key_with(&self, input: &str, options: &CollatorOptions)
+        -> Result<Key, OptionsError>
+    {
+        let numeric_primary = self.data.numeric_primary(options)?;
+        let result = self.data.get_weights(input, options, numeric_primary);
+
+        let reordering = Reordering::new(options, self.data.groups)?;
+        let mut key = compose_reordered_key(&result, options, &reordering);
+
+        if options.strength == Strength::Identical {
+            key.push_identical_level(&nfd(input));
+        }
+
+        Ok(key)
     }
 
     /// ключ как вектор весов
     #[inline(always)]
     pub fn get_weights(&self, input: &str) -> Vec<u32>
     {
-        let mut codepoints = CodepointsIter::new(
-            input,
-            &self.scalars64,
-            &self.scalars32,
-            &self.index,
-            self.continuous_block_end,
-        );
+        self.data
+            .get_weights(input, &self.options, self.numeric_primary)
+    }
+
+    /// опции коллатора
+    pub fn options(&self) -> &CollatorOptions
+    {
+        &self.options
+    }
+
+    /// коллатор с другими опциями и теми же таблицами весов (таблицы не копируются)
+    pub fn with_options(&self, options: CollatorOptions) -> Result<Self, OptionsError>
+    {
+        Self::from_data_ref(self.data.clone(), options)
+    }
+
+    /// состояние получения весов строки - для получения весов порциями
+    #[inline(always)]
+    fn weights_stream<'s>(&'s self, input: &'s str) -> WeightsStream<'s>
+    {
+        self.data
+            .weights_stream(input, &self.options, self.numeric_primary)
+    }
+
+    /// получение весов строки порциями (см. CollatorData::weights_loop)
+    #[inline(always)]
+    fn weights_loop(&self, stream: &mut WeightsStream, result: &mut Vec<u32>, limit: usize)
+    {
+        self.data.weights_loop(stream, result, limit)
+    }
+
+    /// новый коллатор с таблицами CLDR (root), таблицы не копируются
+    pub fn new(options: CollatorOptions) -> Self
+    {
+        // таблицы CLDR поддерживают все опции
+        match Self::from_static(&CLDR_UND, options) {
+            Ok(collator) => collator,
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// создать коллатор из заранее подготовленных данных
+    ///
+    /// # Panics
+    ///
+    /// опции не поддерживаются таблицами (см. OptionsError) - например, reorder или maxVariable,
+    /// отличный от значения по умолчанию, у таблиц без границ групп первичных весов. с опциями по
+    /// умолчанию не паникует
+    #[deprecated(note = "используйте Collator::try_from_baked или Collator::from_data")]
+    pub fn from_baked(weights_data: WeightsData, options: CollatorOptions) -> Self
+    {
+        match Self::from_data(Arc::new(CollatorData::from_baked(weights_data)), options) {
+            Ok(collator) => collator,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// создать коллатор из заранее подготовленных данных, предварительно проверив их
+    pub fn try_from_baked(
+        weights_data: WeightsData,
+        options: CollatorOptions,
+    ) -> Result<Self, DataError>
+    {
+        Self::from_data(
+            Arc::new(CollatorData::try_from_baked(weights_data)?),
+            options,
+        )
+        .map_err(DataError::UnsupportedOptions)
+    }
+
+    /// создать коллатор из двоичного контейнера таблиц (см. container) - таблицы проверяются, но
+    /// не копируются
+    pub fn from_bytes(bytes: &'a [u8], options: CollatorOptions) -> Result<Self, ContainerError>
+    {
+        Self::from_data(Arc::new(CollatorData::from_bytes(bytes)?), options)
+            .map_err(ContainerError::UnsupportedOptions)
+    }
+
+    /// создать коллатор из общих таблиц весов
+    pub fn from_data(
+        data: Arc<CollatorData<'a>>,
+        options: CollatorOptions,
+    ) -> Result<Self, OptionsError>
+    {
+        Self::from_data_ref(DataRef::Shared(data), options)
+    }
+
+    /// создать коллатор из таблиц весов, которые живут дольше коллатора (например, static)
+    pub fn from_static(
+        data: &'a CollatorData<'a>,
+        options: CollatorOptions,
+    ) -> Result<Self, OptionsError>
+    {
+        Self::from_data_ref(DataRef::Static(data), options)
+    }
+
+    fn from_data_ref(data: DataRef<'a>, options: CollatorOptions) -> Result<Self, OptionsError>
+    {
+        Ok(Self {
+            numeric_primary: data.numeric_primary(&options)?,
+            reordering: Reordering::new(&options, data.groups)?,
+            data,
+            options,
+        })
+    }
+}
+
+impl<'a> Default for CollatorData<'a>
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl<'a> CollatorData<'a>
+{
+    /// таблицы весов CLDR (root) - единственные таблицы с известными границами групп первичных
+    /// весов (см. OptionsError)
+    pub const fn new() -> Self
+    {
+        let mut data = Self::from_static(data::cldr_und());
+
+        data.groups = Some(&CLDR_UND_GROUPS);
+
+        data
+    }
+
+    /// таблицы весов из двоичного контейнера (см. container) - с проверкой, без копирования
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ContainerError>
+    {
+        let (weights_data, _) = container::read(bytes)?;
+
+        validate::validate(&weights_data).map_err(ContainerError::InvalidData)?;
+
+        Ok(Self::from_static(weights_data))
+    }
+
+    /// таблицы весов из выровненных данных (см. AlignedArray, container) - без копирования
+    pub const fn from_static(weights_data: WeightsData<'a>) -> Self
+    {
+        Self {
+            scalars64: Aligned::borrowed(weights_data.scalars64),
+            scalars32: Aligned::borrowed(weights_data.scalars32),
+            index: Aligned::borrowed(weights_data.index),
+            expansions: Aligned::borrowed(weights_data.expansions),
+            tries: Aligned::borrowed(weights_data.tries),
+            continuous_block_end: weights_data.continuous_block_end,
+            groups: None,
+        }
+    }
 
+    /// таблицы весов из заранее подготовленных данных с проверкой (см. DataError)
+    pub fn try_from_baked(weights_data: WeightsData) -> Result<Self, DataError>
+    {
+        validate::validate(&weights_data)?;
+
+        Ok(Self::from_baked(weights_data))
+    }
+
+    /// таблицы весов из заранее подготовленных данных (данные копируются в выровненные блоки)
+    pub fn from_baked(weights_data: WeightsData) -> Self
+    {
+        Self {
+            scalars64: Aligned::from(weights_data.scalars64),
+            scalars32: Aligned::from(weights_data.scalars32),
+            index: Aligned::from(weights_data.index),
+            expansions: Aligned::from(weights_data.expansions),
+            tries: Aligned::from(weights_data.tries),
+            continuous_block_end: weights_data.continuous_block_end,
+            groups: None,
+        }
+    }
+
+    /// первичный вес цифры 0 (U+0030) для числового сопоставления, 0 - numeric не используется
+    fn numeric_primary(&self, options: &CollatorOptions) -> Result<u16, OptionsError>
+    {
+        if !options.numeric {
+            return Ok(0);
+        }
+
+        match self
+            .get_weights("0", &CollatorOptions::default(), 0)
+            .first()
+        {
+            Some(&weights) if weights as u16 != 0 => Ok(weights as u16),
+            _ => Err(OptionsError::Numeric),
+        }
+    }
+
+    /// веса строки с заданными опциями
+    #[inline(always)]
+    fn get_weights(&self, input: &str, options: &CollatorOptions, numeric_primary: u16)
+        -> Vec<u32>
+    {
+        let mut stream = self.weights_stream(input, options, numeric_primary);
         let mut result = Vec::<u32>::with_capacity(input.len());
-        let mut buffer = Vec::<CollationElement>::new();
 
-        self.ce_buffer_loop(&mut codepoints, &mut result, &mut buffer);
+        self.weights_loop(&mut stream, &mut result, usize::MAX);
 
         result
     }
 
+    /// состояние получения весов строки - для получения весов порциями
+    #[inline(always)]
+    fn weights_stream<'s>(
+        &'s self,
+        input: &'s str,
+        options: &CollatorOptions,
+        numeric_primary: u16,
+    ) -> WeightsStream<'s>
+    {
+        WeightsStream::new(
+            CodepointsIter::new(
+                input,
+                &self.scalars64,
+                &self.scalars32,
+                &self.index,
+                self.continuous_block_end,
+                options.numeric,
+            ),
+            options,
+            numeric_primary,
+        )
+    }
+
+    /// получение весов строки порциями (см. ce_buffer_loop), с учётом числового сопоставления:
+    /// итератор кодпоинтов останавливается перед цифрами, последовательность цифр заменяется
+    /// весами её числового значения
+    #[inline(always)]
+    fn weights_loop(&self, stream: &mut WeightsStream, result: &mut Vec<u32>, limit: usize)
+    {
+        loop {
+            self.ce_buffer_loop(stream, result, limit);
+
+            if !stream.is_finished || !stream.numeric {
+                return;
+            }
+
+            let digits = stream.codepoints.take_digits();
+
+            if digits.is_empty() {
+                return;
+            }
+
+            write_numeric_weights(digits, stream.numeric_primary, result);
+
+            // буфер пуст - продолжаем как после стартера
+            stream.previous_ccc = 0;
+            stream.is_finished = false;
+        }
+    }
+
     /// быстрый цикл - только стартеры (сразу пишем результат без использования буфера)
     #[inline(always)]
     fn starters_loop(
         &self,
         codepoints: &mut CodepointsIter,
         result: &mut Vec<u32>,
+        limit: usize,
     ) -> Option<CodepointWithData>
     {
-        loop {
+        while result.len() < limit {
             let codepoint = codepoints.next()?;
 
             match codepoint.marker() {
@@ -117,30 +450,42 @@ impl<'a> Collator<'a>
                 _ => return Some(codepoint),
             }
         }
+
+        None
     }
 
     /// цикл с использованием буфера кодпоинтов (не делаем декомпозицию, когда она не нужна)
+    ///
+    /// обработка прерывается, как только в результате окажется не менее limit весов,
+    /// состояние сохраняется в stream - можно продолжить с того же места
     #[inline(always)]
-    fn ce_buffer_loop(
-        &'a self,
-        codepoints: &mut CodepointsIter,
-        result: &mut Vec<u32>,
-        buffer: &mut Vec<CollationElement>,
-    )
+    fn ce_buffer_loop(&self, stream: &mut WeightsStream, result: &mut Vec<u32>, limit: usize)
     {
-        let mut previous_ccc = 0;
+        let codepoints = &mut stream.codepoints;
+        let buffer = &mut stream.buffer;
+        let previous_ccc = &mut stream.previous_ccc;
+        let normalization = stream.normalization;
 
         loop {
+            if result.len() >= limit {
+                return;
+            }
+
             // самый частый случай - последовательно идущие обычные стартеры, для них - цикл без избыточных проверок
             let mut codepoint = match buffer.is_empty() {
-                true => match self.starters_loop(codepoints, result) {
+                true => match self.starters_loop(codepoints, result, limit) {
                     Some(codepoint) => codepoint,
-                    None => return,
+                    None => {
+                        // цикл стартеров мог завершиться из-за достижения лимита
+                        stream.is_finished = result.len() < limit;
+                        return;
+                    }
                 },
                 false => match codepoints.next() {
                     Some(codepoint) => codepoint,
                     None => {
-                        self.handle_buffer(result, buffer, previous_ccc != 0xFF);
+                        self.handle_buffer(result, buffer, *previous_ccc != 0xFF);
+                        stream.is_finished = true;
                         return;
                     }
                 },
@@ -157,25 +502,25 @@ impl<'a> Collator<'a>
                 match codepoint.marker() {
                     // стартеры, синглтоны
                     MARKER_STARTER_SINGLE_WEIGHTS => {
-                        self.handle_buffer(result, buffer, previous_ccc != 0xFF);
+                        self.handle_buffer(result, buffer, *previous_ccc != 0xFF);
 
                         result.push(codepoint.single_weights());
 
-                        previous_ccc = 0;
+                        *previous_ccc = 0;
                     }
                     // расширения стартеров
                     MARKER_STARTER_EXPANSION => {
-                        self.handle_buffer(result, buffer, previous_ccc != 0xFF);
+                        self.handle_buffer(result, buffer, *previous_ccc != 0xFF);
 
                         result.extend_from_slice(codepoint.expansion_weights(&self.expansions));
 
-                        previous_ccc = 0;
+                        *previous_ccc = 0;
                     }
                     // декомпозиция, начинается со стартера
                     MARKER_STARTER_DECOMPOSITION => {
-                        self.handle_buffer(result, buffer, previous_ccc != 0xFF);
+                        self.handle_buffer(result, buffer, *previous_ccc != 0xFF);
 
-                        previous_ccc = match codepoint.ccc_or_len() {
+                        *previous_ccc = match codepoint.ccc_or_len() {
                             // частный случай - слог хангыль
                             MARKER_CCC_HANGUL => {
                                 write_hangul_syllable(codepoint.code, result);
@@ -189,13 +534,13 @@ impl<'a> Collator<'a>
                     }
                     // стартер, начало последовательности (сокращение или many-to-many)
                     MARKER_STARTER_TRIE => {
-                        self.handle_buffer(result, buffer, previous_ccc != 0xFF);
+                        self.handle_buffer(result, buffer, *previous_ccc != 0xFF);
 
                         pending = self.handle_starter_trie(codepoint, result, buffer, codepoints);
 
                         // если буфер не пуст (содержит узел), то это означает, что возможно
                         // продолжение последовательности с далее идущими нестартерами
-                        previous_ccc = match buffer.is_empty() {
+                        *previous_ccc = match buffer.is_empty() {
                             true => 0,
                             false => 0xFF,
                         };
@@ -204,34 +549,28 @@ impl<'a> Collator<'a>
                     MARKER_NONSTARTER_SINGLE_WEIGHTS => {
                         let ce = codepoint.as_ce_single_weights();
 
-                        // потребуется декомпозиция - нарушен порядок CCC
-                        previous_ccc = match ce.ccc < previous_ccc {
-                            true => 0xFF,
-                            false => ce.ccc,
-                        };
+                        *previous_ccc = nonstarter_ccc(normalization, *previous_ccc, ce.ccc);
 
                         buffer.push(ce);
                     }
                     // нестартер - расширение, сокращение или декомпозиция
                     MARKER_NONSTARTER_TRIE => {
-                        let mut trie_iter = TrieIter::new(&self.tries, codepoint.data_pos());
+                        let trie_iter = TrieIter::new(&self.tries, codepoint.data_pos());
 
-                        while let Some(node) = trie_iter.next() {
+                        for node in trie_iter {
                             let ccc = node.ccc();
 
                             // кодпоинт - начало последовательности / обычное расширение
                             // декомпозицию придётся делать, если нарушен порядок CCC или кодпоинт - начало последовательности
                             match node.has_children() {
                                 true => {
-                                    previous_ccc = 0xFF;
+                                    *previous_ccc = 0xFF;
 
                                     buffer.push(node.as_ce_trie());
                                 }
                                 false => {
-                                    previous_ccc = match ccc < previous_ccc {
-                                        true => 0xFF,
-                                        false => ccc,
-                                    };
+                                    *previous_ccc =
+                                        nonstarter_ccc(normalization, *previous_ccc, ccc);
 
                                     buffer.push(node.as_ce_weights());
                                 }
@@ -240,11 +579,11 @@ impl<'a> Collator<'a>
                     }
                     // вычисляемые веса
                     MARKER_IMPLICIT => {
-                        self.handle_buffer(result, buffer, previous_ccc != 0xFF);
+                        self.handle_buffer(result, buffer, *previous_ccc != 0xFF);
 
                         result.extend_from_slice(&implicit_weights(codepoint.code));
 
-                        previous_ccc = 0;
+                        *previous_ccc = 0;
                     }
                     _ => unreachable!(),
                 }
@@ -352,7 +691,7 @@ impl<'a> Collator<'a>
 
         // получаем следующий кодпоинт
         let mut second = codepoints.next_or_else(|| {
-            result.extend_from_slice(&node.weights(&self.tries));
+            result.extend_from_slice(node.weights(&self.tries));
         })?;
 
         // встретили обычный стартер
@@ -360,7 +699,7 @@ impl<'a> Collator<'a>
             loop {
                 // следующий потомок текущего узла, не нашли - пишем веса текущиго узела + веса стартера
                 let child_node = children.next_or_else(|| {
-                    result.extend_from_slice(&node.weights(&self.tries));
+                    result.extend_from_slice(node.weights(&self.tries));
                     second.write_starter_weights(result, &self.expansions);
                 })?;
 
@@ -368,7 +707,7 @@ impl<'a> Collator<'a>
                 if child_node.code() == second.code {
                     // потомков нет - записываем веса текущего узла
                     if !(child_node.has_children()) {
-                        result.extend_from_slice(&child_node.weights(&self.tries));
+                        result.extend_from_slice(child_node.weights(&self.tries));
 
                         return None;
                     }
@@ -377,7 +716,7 @@ impl<'a> Collator<'a>
                     node = child_node;
 
                     second = codepoints.next_or_else(|| {
-                        result.extend_from_slice(&node.weights(&self.tries));
+                        result.extend_from_slice(node.weights(&self.tries));
                     })?;
 
                     // получили кодпоинт, который не является стартером - элементом последовательности
@@ -394,7 +733,7 @@ impl<'a> Collator<'a>
                 // проверяемый стартер отсутствует среди возможных комбинаций
                 // пишем оба CE в результат
                 if child_node.ccc() != 0 {
-                    result.extend_from_slice(&node.weights(&self.tries));
+                    result.extend_from_slice(node.weights(&self.tries));
                     second.write_starter_weights(result, &self.expansions);
 
                     return None;
@@ -425,7 +764,7 @@ impl<'a> Collator<'a>
         let mut ce = match index < buffer.len() {
             true => buffer[index],
             false => {
-                result.extend_from_slice(&node.weights(&self.tries));
+                result.extend_from_slice(node.weights(&self.tries));
 
                 return;
             }
@@ -550,35 +889,25 @@ impl<'a> Collator<'a>
 
         buffer[0] = decomposition.next().unwrap().as_ce_weights();
 
-        let mut i = 1;
-
-        while let Some(nonstarter) = decomposition.next() {
+        for (i, nonstarter) in (1 ..).zip(decomposition) {
             buffer.insert(i, nonstarter.as_ce_weights());
-            i += 1;
         }
 
         buffer.sort_by_key(|ce| ce.ccc);
 
         starter
     }
+}
 
-    /// новый коллатор
-    pub fn new(options: CollatorOptions) -> Self
-    {
-        Self::from_baked(data::cldr_und(), options)
-    }
-
-    /// создать коллатор из заранее подготовленных данных
-    pub fn from_baked(weights_data: WeightsData, options: CollatorOptions) -> Self
-    {
-        Self {
-            scalars64: Aligned::from(weights_data.scalars64),
-            scalars32: Aligned::from(weights_data.scalars32),
-            index: Aligned::from(weights_data.index),
-            expansions: Aligned::from(weights_data.expansions),
-            tries: Aligned::from(weights_data.tries),
-            continuous_block_end: weights_data.continuous_block_end,
-            options,
-        }
+/// CCC последнего кодпоинта буфера после добавления нестартера, 0xFF - потребуется декомпозиция
+///
+/// нарушенный порядок CCC учитывается только при включённой нормализации - иначе считаем, что
+/// строка в форме FCD и нестартеры уже идут в нужном порядке
+#[inline(always)]
+fn nonstarter_ccc(normalization: bool, previous_ccc: u8, ccc: u8) -> u8
+{
+    match ccc < previous_ccc && (normalization || previous_ccc == 0xFF) {
+        true => 0xFF,
+        false => ccc,
     }
 }
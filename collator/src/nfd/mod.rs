@@ -0,0 +1,94 @@
+use crate::data::{self, NormalizationData};
+use crate::hangul::{decompose_hangul_syllable, is_hangul_syllable};
+
+/// строка в форме NFD (каноническая декомпозиция + канонический порядок нестартеров) в виде кодпоинтов
+pub fn nfd(input: &str) -> Vec<u32>
+{
+    let data = data::nfd();
+
+    let mut result = Vec::with_capacity(input.len());
+
+    for code in input.chars().map(u32::from) {
+        decompose(&data, code, &mut result);
+    }
+
+    // сортируем последовательности нестартеров по CCC (сортировка стабильная)
+    let mut start = 0;
+
+    while start < result.len() {
+        if ccc(&data, result[start]) == 0 {
+            start += 1;
+            continue;
+        }
+
+        let mut end = start + 1;
+
+        while end < result.len() && ccc(&data, result[end]) != 0 {
+            end += 1;
+        }
+
+        if end - start > 1 {
+            result[start .. end].sort_by_key(|&code| ccc(&data, code));
+        }
+
+        start = end;
+    }
+
+    result
+}
+
+/// строка в форме FCD: после канонической декомпозиции каждого кодпоинта по отдельности
+/// нестартеры уже идут в каноническом порядке (UTN #5). для таких строк можно отключить
+/// нормализацию в опциях коллатора
+pub fn is_fcd(input: &str) -> bool
+{
+    let data = data::nfd();
+
+    let mut decomposition = vec![];
+    let mut previous_trail_ccc = 0;
+
+    for code in input.chars().map(u32::from) {
+        decomposition.clear();
+        decompose(&data, code, &mut decomposition);
+
+        let lead_ccc = ccc(&data, decomposition[0]);
+
+        if lead_ccc != 0 && lead_ccc < previous_trail_ccc {
+            return false;
+        }
+
+        previous_trail_ccc = ccc(&data, decomposition[decomposition.len() - 1]);
+    }
+
+    true
+}
+
+/// CCC кодпоинта
+#[inline]
+pub fn ccc(data: &NormalizationData, code: u32) -> u8
+{
+    match data.ccc.binary_search_by_key(&code, |&entry| entry >> 8) {
+        Ok(index) => data.ccc[index] as u8,
+        Err(_) => 0,
+    }
+}
+
+/// полная каноническая декомпозиция кодпоинта (без сортировки по CCC)
+#[inline]
+pub fn decompose(data: &NormalizationData, code: u32, result: &mut Vec<u32>)
+{
+    if is_hangul_syllable(code) {
+        decompose_hangul_syllable(code, result);
+        return;
+    }
+
+    match data.codes.binary_search(&code) {
+        Ok(index) => {
+            let start = data.offsets[index] as usize;
+            let end = data.offsets[index + 1] as usize;
+
+            result.extend_from_slice(&data.decompositions[start .. end]);
+        }
+        Err(_) => result.push(code),
+    }
+}
@@ -0,0 +1,94 @@
+// числовое сопоставление (kn): последовательность десятичных цифр заменяется весами её значения
+//
+// веса числа: [.ZZZZ.0020.0002][.LLLL.0000.0000][.VVVV.0000.0000]...
+// ZZZZ - первичный вес цифры 0 (U+0030) в таблицах коллатора, LLLL - количество значащих цифр + 1,
+// VVVV - группы по 4 цифры
+/// L2, L3 первого веса числа - общие веса
+const NUMERIC_L2_L3: u32 = (0x20 << 16) | (0x02 << 25);
+/// максимальное количество значащих цифр в одном числе, более длинные последовательности
+/// разбиваются на несколько чисел (как в ICU). вес длины при этом не превышает 0xFF - он меньше
+/// любого первичного веса, кроме веса U+FFFE
+const MAX_DIGITS: usize = 254;
+/// количество цифр в одном весе
+const DIGITS_PER_WEIGHT: usize = 4;
+
+/// кодпоинты цифры 0 десятичных систем счисления (General_Category = Nd), за каждым из них
+/// следуют цифры 1 ..= 9
+const DIGIT_ZEROS: [u32; 68] = [
+    0x30, 0x660, 0x6F0, 0x7C0, 0x966, 0x9E6, 0xA66, 0xAE6, 0xB66, 0xBE6, 0xC66, 0xCE6, 0xD66,
+    0xDE6, 0xE50, 0xED0, 0xF20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80, 0x1A90,
+    0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0, 0xFF10,
+    0x104A0, 0x10D30, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0, 0x11650,
+    0x116C0, 0x11730, 0x118E0, 0x11950, 0x11C50, 0x11D50, 0x11DA0, 0x11F50, 0x16A60, 0x16AC0,
+    0x16B50, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0, 0x1E950,
+    0x1FBF0,
+];
+
+/// значение десятичной цифры (Nd), None - кодпоинт не является десятичной цифрой
+#[inline(always)]
+pub fn digit_value(code: u32) -> Option<u8>
+{
+    // ASCII - самый частый случай
+    if code < 0x80 {
+        return match (0x30 ..= 0x39).contains(&code) {
+            true => Some((code - 0x30) as u8),
+            false => None,
+        };
+    }
+
+    let zero = match DIGIT_ZEROS.binary_search(&code) {
+        Ok(index) => DIGIT_ZEROS[index],
+        Err(0) => return None,
+        Err(index) => DIGIT_ZEROS[index - 1],
+    };
+
+    match code - zero < 10 {
+        true => Some((code - zero) as u8),
+        false => None,
+    }
+}
+
+/// записать веса последовательности десятичных цифр, primary - первичный вес цифры 0
+///
+/// ведущие нули не учитываются (но число, состоящее из нулей, - это 0). веса всех цифр - общие
+/// на вторичном и третичном уровнях, т.е. числа, записанные цифрами разных систем счисления,
+/// различаются только на идентичном уровне
+pub fn write_numeric_weights(digits: &str, primary: u16, result: &mut Vec<u32>)
+{
+    let digits: Vec<u8> = digits
+        .chars()
+        .filter_map(|c| digit_value(c as u32))
+        .collect();
+
+    let mut pos = 0;
+
+    while pos < digits.len() {
+        while pos < digits.len() - 1 && digits[pos] == 0 {
+            pos += 1;
+        }
+
+        let len = core::cmp::min(digits.len() - pos, MAX_DIGITS);
+
+        write_number(&digits[pos .. pos + len], primary, result);
+
+        pos += len;
+    }
+}
+
+/// записать веса числа из не более чем MAX_DIGITS цифр
+///
+/// числа с большим количеством цифр - больше, при равном количестве цифры сравниваются группами
+#[inline(always)]
+fn write_number(digits: &[u8], primary: u16, result: &mut Vec<u32>)
+{
+    result.push(primary as u32 | NUMERIC_L2_L3);
+    result.push(digits.len() as u32 + 1);
+
+    for group in digits.chunks(DIGITS_PER_WEIGHT) {
+        let value = group
+            .iter()
+            .fold(0, |value, &digit| value * 10 + digit as u32);
+
+        result.push(value | 0x8000);
+    }
+}
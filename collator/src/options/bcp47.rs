@@ -0,0 +1,289 @@
+// опции коллатора в виде расширения -u- тега BCP 47 (UTS #35, Unicode Locale Identifier):
+// und-u-ks-level2-ka-shifted-kr-cyrl-latn-digit
+
+use core::fmt;
+
+use super::*;
+
+/// ошибка разбора или записи опций в виде тега BCP 47
+#[derive(Debug, Clone, PartialEq)]
+pub enum Bcp47Error
+{
+    /// тег записан с нарушением синтаксиса (пустой подтег)
+    InvalidTag(String),
+    /// ключ указан в расширении более одного раза
+    DuplicateKey(String),
+    /// неизвестный ключ сопоставления
+    UnsupportedKey(String),
+    /// недопустимое значение ключа
+    InvalidValue
+    {
+        key: String, value: String
+    },
+    /// допустимое значение, которое не поддерживается коллатором (например, co-phonebk), или
+    /// опция, которую нельзя записать в виде тега (например, AlternateHandling::Blanked)
+    UnsupportedValue
+    {
+        key: String, value: String
+    },
+}
+
+impl fmt::Display for Bcp47Error
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            Self::InvalidTag(tag) => write!(f, "некорректный тег BCP 47: \"{}\"", tag),
+            Self::DuplicateKey(key) => write!(f, "ключ \"{}\" указан более одного раза", key),
+            Self::UnsupportedKey(key) => write!(f, "неизвестный ключ сопоставления \"{}\"", key),
+            Self::InvalidValue { key, value } => {
+                write!(f, "недопустимое значение ключа \"{}\": \"{}\"", key, value)
+            }
+            Self::UnsupportedValue { key, value } => {
+                write!(
+                    f,
+                    "значение ключа \"{}\" не поддерживается: \"{}\"",
+                    key, value
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Bcp47Error {}
+
+impl CollatorOptions
+{
+    /// опции из тега BCP 47: ключи co, ka, kb, kc, kf, kk, kn, kr, ks, kv расширения -u-
+    ///
+    /// ключи, не относящиеся к сопоставлению (ca, nu и т.д.), пропускаются, неизвестные ключи
+    /// сопоставления и недопустимые значения - ошибка. опции, не указанные в теге, - по умолчанию
+    pub fn from_bcp47(tag: &str) -> Result<Self, Bcp47Error>
+    {
+        let lowercase = tag.to_ascii_lowercase();
+        let mut subtags = lowercase.split(['-', '_']);
+
+        if subtags.clone().any(|subtag| subtag.is_empty()) {
+            return Err(Bcp47Error::InvalidTag(tag.to_owned()));
+        }
+
+        let mut options = Self::default();
+
+        // подтеги языка и других расширений, затем - расширение -u- до следующего синглтона. после
+        // x - подтеги частного использования, -u- в них - не расширение
+        if !subtags
+            .by_ref()
+            .take_while(|subtag| *subtag != "x")
+            .any(|subtag| subtag == "u")
+        {
+            return Ok(options);
+        }
+
+        let mut keys: Vec<(&str, Vec<&str>)> = vec![];
+
+        for subtag in subtags.take_while(|subtag| subtag.len() > 1) {
+            if subtag.len() == 2 {
+                if keys.iter().any(|(key, _)| *key == subtag) {
+                    return Err(Bcp47Error::DuplicateKey(subtag.to_owned()));
+                }
+
+                keys.push((subtag, vec![]));
+                continue;
+            }
+
+            // атрибуты расширения (до первого ключа) пропускаем
+            if let Some((_, values)) = keys.last_mut() {
+                values.push(subtag);
+            }
+        }
+
+        for (key, values) in keys {
+            options.apply_bcp47_key(key, &values)?;
+        }
+
+        Ok(options)
+    }
+
+    /// опции в виде тега BCP 47 (und-u-...), указываются только значения, отличные от значений по
+    /// умолчанию. quaternary_mode в тег не записывается
+    pub fn to_bcp47(&self) -> Result<String, Bcp47Error>
+    {
+        let defaults = Self::default();
+        let mut tag = String::from("und");
+
+        let mut push = |key: &str, value: &str| {
+            if tag.len() == 3 {
+                tag.push_str("-u");
+            }
+
+            tag.push('-');
+            tag.push_str(key);
+
+            if !value.is_empty() {
+                tag.push('-');
+                tag.push_str(value);
+            }
+        };
+
+        match self.alternate {
+            AlternateHandling::NonIgnorable => (),
+            AlternateHandling::Shifted => push("ka", "shifted"),
+            alternate => {
+                return Err(Bcp47Error::UnsupportedValue {
+                    key: "ka".to_owned(),
+                    value: format!("{:?}", alternate),
+                })
+            }
+        }
+
+        if self.backwards_secondary {
+            push("kb", "");
+        }
+
+        if self.case_level {
+            push("kc", "");
+        }
+
+        match self.case_first {
+            CaseFirst::Off => (),
+            CaseFirst::Upper => push("kf", "upper"),
+            CaseFirst::Lower => push("kf", "lower"),
+        }
+
+        if !self.normalization {
+            push("kk", "false");
+        }
+
+        if self.numeric {
+            push("kn", "");
+        }
+
+        if !self.reorder.is_empty() {
+            let codes: Vec<String> = self
+                .reorder
+                .iter()
+                .map(|code| code.code().to_ascii_lowercase())
+                .collect();
+
+            push("kr", &codes.join("-"));
+        }
+
+        if self.strength != defaults.strength {
+            push(
+                "ks",
+                match self.strength {
+                    Strength::Primary => "level1",
+                    Strength::Secondary => "level2",
+                    Strength::Tetriary => "level3",
+                    Strength::Quaternary => "level4",
+                    Strength::Identical => "identic",
+                },
+            );
+        }
+
+        if self.max_variable != defaults.max_variable {
+            push(
+                "kv",
+                match self.max_variable {
+                    MaxVariable::Space => "space",
+                    MaxVariable::Punct => "punct",
+                    MaxVariable::Symbol => "symbol",
+                    MaxVariable::Currency => "currency",
+                },
+            );
+        }
+
+        Ok(tag)
+    }
+
+    /// применить ключ расширения -u-
+    fn apply_bcp47_key(&mut self, key: &str, values: &[&str]) -> Result<(), Bcp47Error>
+    {
+        let invalid = || Bcp47Error::InvalidValue {
+            key: key.to_owned(),
+            value: values.join("-"),
+        };
+
+        // ключи с единственным значением, true можно не указывать
+        let value = match values {
+            [] => "true",
+            [value] => value,
+            _ if key == "kr" => "",
+            _ => return Err(invalid()),
+        };
+
+        let boolean = || match value {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            _ => Err(invalid()),
+        };
+
+        match key {
+            "co" => match value {
+                "standard" => (),
+                "true" => return Err(invalid()),
+                _ => {
+                    return Err(Bcp47Error::UnsupportedValue {
+                        key: key.to_owned(),
+                        value: value.to_owned(),
+                    })
+                }
+            },
+            "ka" => {
+                self.alternate = match value {
+                    "noignore" => AlternateHandling::NonIgnorable,
+                    "shifted" => AlternateHandling::Shifted,
+                    _ => return Err(invalid()),
+                }
+            }
+            "kb" => self.backwards_secondary = boolean()?,
+            "kc" => self.case_level = boolean()?,
+            "kf" => {
+                self.case_first = match value {
+                    "upper" => CaseFirst::Upper,
+                    "lower" => CaseFirst::Lower,
+                    "false" => CaseFirst::Off,
+                    _ => return Err(invalid()),
+                }
+            }
+            "kk" => self.normalization = boolean()?,
+            "kn" => self.numeric = boolean()?,
+            "kr" => {
+                self.reorder = match values.is_empty() {
+                    true => return Err(invalid()),
+                    false => values
+                        .iter()
+                        .map(|&code| ReorderCode::from_code(code).ok_or_else(invalid))
+                        .collect::<Result<_, _>>()?,
+                }
+            }
+            "ks" => {
+                self.strength = match value {
+                    "level1" => Strength::Primary,
+                    "level2" => Strength::Secondary,
+                    "level3" => Strength::Tetriary,
+                    "level4" => Strength::Quaternary,
+                    "identic" => Strength::Identical,
+                    _ => return Err(invalid()),
+                }
+            }
+            "kv" => {
+                self.max_variable = match value {
+                    "space" => MaxVariable::Space,
+                    "punct" => MaxVariable::Punct,
+                    "symbol" => MaxVariable::Symbol,
+                    "currency" => MaxVariable::Currency,
+                    _ => return Err(invalid()),
+                }
+            }
+            // прочие ключи сопоставления (kh, vt и т.д.)
+            _ if key.starts_with('k') || key == "vt" => {
+                return Err(Bcp47Error::UnsupportedKey(key.to_owned()))
+            }
+            // ключи, не относящиеся к сопоставлению
+            _ => (),
+        }
+
+        Ok(())
+    }
+}
@@ -1,14 +1,189 @@
-use super::CollatorOptions;
+use core::fmt;
 
-/// числовое значение опций - для сохранения, битовых операций
-#[derive(Copy, Clone)]
-pub struct CollatorOptionsValue(u16);
+use super::{
+    AlternateHandling, CaseFirst, CollatorOptions, MaxVariable, QuaternaryMode, ReorderCode,
+    Strength,
+};
 
-impl From<CollatorOptionsValue> for u16
+// формат сохранённых опций:
+//
+// версия 0 - u16 (прежний формат CollatorOptionsValue): флаги без порядка групп
+// версия 1 - байты: [версия] [флаги u16 BE] [группы reorder по 4 байта]...
+//
+// флаги (одинаковы в обеих версиях):
+//
+// 0 ..= 2      strength (1 ..= 5)
+// 3            alternate, младший бит
+// 4 ..= 5      case_first (0 ..= 2)
+// 6            case_level
+// 7            backwards_secondary
+// 8            numeric
+// 9 ..= 10     max_variable: 0 - Punct, 1 - Space, 2 - Symbol, 3 - Currency (нулевое значение -
+//              значение по умолчанию: в прежнем формате эти биты не задавались)
+// 11           alternate, старший бит (Blanked / ShiftTrimmed)
+// 12           quaternary_mode
+// 13           нормализация отключена
+// 14 ..= 15    зарезервированы для новых опций, 0
+//
+// группа reorder: код письменности ISO 15924 (4 ASCII-символа) или специальная группа [0, 0, 0, N]
+
+/// текущая версия формата
+const VERSION: u8 = 1;
+/// биты флагов, зарезервированные для новых опций
+const RESERVED_FLAGS: u16 = 0xC000;
+
+/// специальные группы reorder в порядке их номеров в сохранённом значении
+const SPECIAL_REORDER_CODES: [ReorderCode; 6] = [
+    ReorderCode::Space,
+    ReorderCode::Punct,
+    ReorderCode::Symbol,
+    ReorderCode::Currency,
+    ReorderCode::Digit,
+    ReorderCode::Others,
+];
+
+/// сохраняемое значение опций (все опции, включая порядок групп) - для хранения рядом с ключами
+///
+/// прежний формат (u16) - см. реализации From и TryFrom для u16
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollatorOptionsValue(Vec<u8>);
+
+/// ошибка восстановления опций из сохранённого значения
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptionsValueError
+{
+    /// неизвестная версия формата
+    UnknownVersion(u8),
+    /// длина значения не соответствует формату
+    InvalidLength(usize),
+    /// недопустимое значение флагов
+    InvalidFlags(u16),
+    /// неизвестная группа reorder
+    InvalidReorderCode([u8; 4]),
+    /// порядок групп не записывается в прежний формат (u16)
+    ReorderInLegacyFormat,
+}
+
+impl fmt::Display for OptionsValueError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            Self::UnknownVersion(version) => {
+                write!(f, "неизвестная версия формата опций: {}", version)
+            }
+            Self::InvalidLength(len) => write!(f, "некорректная длина значения опций: {}", len),
+            Self::InvalidFlags(flags) => write!(f, "недопустимые флаги опций: {:#06X}", flags),
+            Self::InvalidReorderCode(code) => write!(f, "неизвестная группа reorder: {:?}", code),
+            Self::ReorderInLegacyFormat => {
+                write!(f, "порядок групп не записывается в прежний формат опций")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OptionsValueError {}
+
+impl CollatorOptionsValue
 {
-    fn from(value: CollatorOptionsValue) -> Self
+    /// значение из сохранённых байт (проверяется при получении опций)
+    pub fn from_bytes(bytes: &[u8]) -> Self
+    {
+        Self(bytes.to_vec())
+    }
+
+    /// байты для сохранения
+    pub fn as_bytes(&self) -> &[u8]
     {
-        value.0
+        &self.0
+    }
+
+    /// флаги и группы reorder (проверяются только версия и длина)
+    fn split(&self) -> Result<(u16, &[u8]), OptionsValueError>
+    {
+        match self.as_bytes() {
+            [0, high, low] => Ok((u16::from_be_bytes([*high, *low]), &[])),
+            [VERSION, high, low, reorder @ ..] if reorder.len() % 4 == 0 => {
+                Ok((u16::from_be_bytes([*high, *low]), reorder))
+            }
+            bytes @ ([0 | VERSION, ..] | []) => Err(OptionsValueError::InvalidLength(bytes.len())),
+            [version, ..] => Err(OptionsValueError::UnknownVersion(*version)),
+        }
+    }
+}
+
+impl From<u16> for CollatorOptionsValue
+{
+    /// значение в прежнем формате (версия 0)
+    fn from(value: u16) -> Self
+    {
+        let [high, low] = value.to_be_bytes();
+
+        Self(vec![0, high, low])
+    }
+}
+
+impl TryFrom<&CollatorOptionsValue> for u16
+{
+    type Error = OptionsValueError;
+
+    /// значение в прежнем формате (версия 0) - возможно, если порядок групп не задан
+    fn try_from(value: &CollatorOptionsValue) -> Result<Self, Self::Error>
+    {
+        match value.split()? {
+            (flags, []) => Ok(flags),
+            _ => Err(OptionsValueError::ReorderInLegacyFormat),
+        }
+    }
+}
+
+impl From<&CollatorOptions> for CollatorOptionsValue
+{
+    fn from(options: &CollatorOptions) -> Self
+    {
+        let flags = options.strength as u16
+            | (((options.alternate as u16) & 1) << 3)
+            | ((options.case_first as u16) << 4)
+            | ((options.case_level as u16) << 6)
+            | ((options.backwards_secondary as u16) << 7)
+            | ((options.numeric as u16) << 8)
+            | (max_variable_bits(options.max_variable) << 9)
+            | (((options.alternate as u16) >> 1) << 11)
+            | ((options.quaternary_mode as u16) << 12)
+            | ((!options.normalization as u16) << 13);
+
+        let mut bytes = Vec::with_capacity(3 + options.reorder.len() * 4);
+
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&flags.to_be_bytes());
+
+        for code in options.reorder.iter() {
+            bytes.extend_from_slice(&match code {
+                ReorderCode::Script(script) => *script,
+                special => {
+                    let index = SPECIAL_REORDER_CODES
+                        .iter()
+                        .position(|code| code == special)
+                        .unwrap();
+
+                    [0, 0, 0, index as u8]
+                }
+            });
+        }
+
+        Self(bytes)
+    }
+}
+
+/// биты max_variable: нулевое значение - Punct, как у значений прежнего формата
+fn max_variable_bits(max_variable: MaxVariable) -> u16
+{
+    match max_variable {
+        MaxVariable::Punct => 0,
+        MaxVariable::Space => 1,
+        MaxVariable::Symbol => 2,
+        MaxVariable::Currency => 3,
     }
 }
 
@@ -16,23 +191,75 @@ impl From<CollatorOptions> for CollatorOptionsValue
 {
     fn from(options: CollatorOptions) -> Self
     {
-        Self(
-            options.strength as u16
-                | ((options.alternate as u16) << 3)
-                
-        )
+        Self::from(&options)
     }
 }
 
-impl From<CollatorOptionsValue> for CollatorOptions
+impl TryFrom<CollatorOptionsValue> for CollatorOptions
 {
-    fn from(value: CollatorOptionsValue) -> Self
+    type Error = OptionsValueError;
+
+    fn try_from(value: CollatorOptionsValue) -> Result<Self, Self::Error>
     {
-        unsafe {
-            Self {
-                strength: core::mem::transmute((value.0 as u8) & 7),
-                alternate: core::mem::transmute(((value.0 as u8) >> 3) & 1),
-            }
+        let (flags, reorder) = value.split()?;
+
+        let invalid = || OptionsValueError::InvalidFlags(flags);
+
+        if flags & RESERVED_FLAGS != 0 {
+            return Err(invalid());
         }
+
+        Ok(Self {
+            strength: match flags & 7 {
+                1 => Strength::Primary,
+                2 => Strength::Secondary,
+                3 => Strength::Tetriary,
+                4 => Strength::Quaternary,
+                5 => Strength::Identical,
+                _ => return Err(invalid()),
+            },
+            alternate: match ((flags >> 3) & 1) | (((flags >> 11) & 1) << 1) {
+                0 => AlternateHandling::NonIgnorable,
+                1 => AlternateHandling::Shifted,
+                2 => AlternateHandling::Blanked,
+                _ => AlternateHandling::ShiftTrimmed,
+            },
+            case_first: match (flags >> 4) & 3 {
+                0 => CaseFirst::Off,
+                1 => CaseFirst::Upper,
+                2 => CaseFirst::Lower,
+                _ => return Err(invalid()),
+            },
+            case_level: (flags >> 6) & 1 != 0,
+            backwards_secondary: (flags >> 7) & 1 != 0,
+            numeric: (flags >> 8) & 1 != 0,
+            max_variable: match (flags >> 9) & 3 {
+                0 => MaxVariable::Punct,
+                1 => MaxVariable::Space,
+                2 => MaxVariable::Symbol,
+                _ => MaxVariable::Currency,
+            },
+            quaternary_mode: match (flags >> 12) & 1 {
+                0 => QuaternaryMode::Cldr,
+                _ => QuaternaryMode::Uca,
+            },
+            // бит установлен при отключённой нормализации - значения без него её включают
+            normalization: (flags >> 13) & 1 == 0,
+            reorder: reorder
+                .chunks_exact(4)
+                .map(|chunk| {
+                    let code: [u8; 4] = chunk.try_into().unwrap();
+
+                    let reorder_code = match code {
+                        [0, 0, 0, index] => SPECIAL_REORDER_CODES.get(index as usize).copied(),
+                        script => core::str::from_utf8(&script)
+                            .ok()
+                            .and_then(ReorderCode::from_code),
+                    };
+
+                    reorder_code.ok_or(OptionsValueError::InvalidReorderCode(code))
+                })
+                .collect::<Result<_, _>>()?,
+        })
     }
 }
@@ -1,61 +1,162 @@
 // все опции - см. UTS #35, https://www.unicode.org/reports/tr35/tr35-collation.html
 
+use core::fmt;
+
+mod bcp47;
 mod compressed;
+mod reorder;
 
-pub use compressed::CollatorOptionsValue;
+pub use bcp47::Bcp47Error;
+pub use compressed::{CollatorOptionsValue, OptionsValueError};
+pub use reorder::ReorderCode;
 
 /// уровень сравнения
 #[repr(u8)]
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Strength
 {
-    Primary = 1,    // базовые символы
-    Secondary = 2,  // диакритические знаки
-    Tetriary = 3,   // регистр / варианты
+    Primary = 1,   // базовые символы
+    Secondary = 2, // диакритические знаки
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "tertiary"))]
+    Tetriary = 3, // регистр / варианты
     Quaternary = 4, // пунктуация
+    Identical = 5,  // кодпоинты NFD-формы строки
 }
 
 /// тип сравнения переменных весов
 #[repr(u8)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum AlternateHandling
 {
+    #[default]
     NonIgnorable = 0, // переменные веса не игнорируются
-    Shifted = 1,      // со сдвигом переменных весов
+    Shifted = 1, // со сдвигом переменных весов
+    Blanked = 2, // переменные веса и следующие за ними игнорируемые полностью игнорируются
+    ShiftTrimmed = 3, // Shifted, конечные веса FFFF четвертичного уровня отбрасываются
+}
+
+/// порядок прописных и строчных на третичном уровне (caseFirst)
+#[repr(u8)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum CaseFirst
+{
+    #[default]
+    Off = 0, // порядок третичных весов
+    Upper = 1, // прописные перед строчными
+    Lower = 2, // строчные перед прописными
+}
+
+/// последняя группа символов, веса которой считаются переменными (maxVariable)
+#[repr(u8)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum MaxVariable
+{
+    Space = 0, // пробелы
+    #[default]
+    Punct = 1, // пробелы и пунктуация
+    Symbol = 2, // пробелы, пунктуация и символы
+    Currency = 3, // пробелы, пунктуация, символы и символы валют
 }
 
-#[derive(Debug, Copy, Clone)]
+/// правила вычисления четвертичного веса в режиме Shifted (см. tests/ISSUES_SHIFTED.md)
+#[repr(u8)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum QuaternaryMode
+{
+    #[default]
+    Cldr = 0, // как в тестах CLDR: FFFF только при L3 ≠ 0, у U+FFFE - 0001
+    Uca = 1,  // как в TR10: FFFF у любого непеременного веса с L1 ≠ 0
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CollatorOptions
 {
     /// уровень сравнения
     pub strength: Strength,
     /// тип сравнения
     pub alternate: AlternateHandling,
+    /// группы символов с переменными весами (для AlternateHandling, кроме NonIgnorable)
+    pub max_variable: MaxVariable,
+    /// правила вычисления четвертичного веса (для Shifted, ShiftTrimmed)
+    pub quaternary_mode: QuaternaryMode,
+    /// порядок регистров
+    pub case_first: CaseFirst,
+    /// уровень регистра между первичным и вторичным уровнями
+    pub case_level: bool,
+    /// вторичные веса сравниваются с конца строки (французский порядок диакритики)
+    pub backwards_secondary: bool,
+    /// последовательности десятичных цифр сравниваются по числовому значению
+    pub numeric: bool,
+    /// порядок групп символов: специальных групп и письменностей
+    pub reorder: Vec<ReorderCode>,
+    /// нормализация строки (kk): при отключении строка считается записанной в форме FCD
+    /// (см. is_fcd), декомпозиция и сортировка нестартеров по CCC не выполняются
+    pub normalization: bool,
 }
 
-impl Default for Strength
+impl Default for CollatorOptions
 {
+    /// в отличие от UTS #35, нормализация по умолчанию включена - результат не зависит от формы
+    /// записи строки
     fn default() -> Self
     {
-        Self::Tetriary
+        Self {
+            strength: Strength::default(),
+            alternate: AlternateHandling::default(),
+            max_variable: MaxVariable::default(),
+            quaternary_mode: QuaternaryMode::default(),
+            case_first: CaseFirst::default(),
+            case_level: false,
+            backwards_secondary: false,
+            numeric: false,
+            reorder: vec![],
+            normalization: true,
+        }
     }
 }
 
-impl Default for AlternateHandling
+/// опции, которые не поддерживаются таблицами весов коллатора
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OptionsError
 {
-    fn default() -> Self
-    {
-        Self::NonIgnorable
-    }
+    /// numeric: у цифры 0 (U+0030) нет первичного веса
+    Numeric,
+    /// maxVariable, отличный от punct: границы групп первичных весов таблиц неизвестны
+    MaxVariable(MaxVariable),
+    /// reorder: границы групп первичных весов таблиц неизвестны
+    Reorder,
 }
 
-impl Default for CollatorOptions
+impl fmt::Display for OptionsError
 {
-    fn default() -> Self
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        Self {
-            strength: Default::default(),
-            alternate: Default::default(),
+        match self {
+            Self::Numeric => write!(f, "numeric: у цифры 0 в таблицах весов нет первичного веса"),
+            Self::MaxVariable(max_variable) => write!(
+                f,
+                "maxVariable = {:?}: границы групп первичных весов таблиц неизвестны",
+                max_variable
+            ),
+            Self::Reorder => write!(
+                f,
+                "reorder: границы групп первичных весов таблиц неизвестны"
+            ),
         }
     }
 }
+
+impl std::error::Error for OptionsError {}
@@ -0,0 +1,80 @@
+/// группа символов для изменения порядка групп (reorder, kr): специальная группа или письменность
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReorderCode
+{
+    Space,    // пробелы
+    Punct,    // пунктуация
+    Symbol,   // символы
+    Currency, // символы валют
+    Digit,    // цифры
+    Others,   // письменности, не указанные в списке (Zzzz)
+    /// письменность, код ISO 15924, например: *b"Cyrl"
+    Script([u8; 4]),
+}
+
+impl ReorderCode
+{
+    /// код группы: space, punct, symbol, currency, digit, others или код письменности
+    pub fn code(&self) -> &str
+    {
+        match self {
+            Self::Space => "space",
+            Self::Punct => "punct",
+            Self::Symbol => "symbol",
+            Self::Currency => "currency",
+            Self::Digit => "digit",
+            Self::Others => "others",
+            Self::Script(script) => core::str::from_utf8(script).unwrap_or("Zzzz"),
+        }
+    }
+
+    /// код группы из строки: space, punct, symbol, currency, digit, others или код письменности
+    /// ISO 15924 (регистр не учитывается). None - неизвестная группа
+    pub fn from_code(code: &str) -> Option<Self>
+    {
+        let group = match code.to_ascii_lowercase().as_str() {
+            "space" => Self::Space,
+            "punct" => Self::Punct,
+            "symbol" => Self::Symbol,
+            "currency" => Self::Currency,
+            "digit" => Self::Digit,
+            "others" | "zzzz" => Self::Others,
+            _ => {
+                let code: [u8; 4] = code.as_bytes().try_into().ok()?;
+
+                let mut script = code.map(|c| c.to_ascii_lowercase());
+                script[0] = script[0].to_ascii_uppercase();
+
+                Self::Script(script)
+            }
+        };
+
+        match group {
+            Self::Script(_) if !crate::reorder::is_known_script(group) => None,
+            group => Some(group),
+        }
+    }
+}
+
+/// сериализуется кодом группы (см. code, from_code)
+#[cfg(feature = "serde")]
+impl serde::Serialize for ReorderCode
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.serialize_str(self.code())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ReorderCode
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        let code = <std::borrow::Cow<str>>::deserialize(deserializer)?;
+
+        Self::from_code(&code).ok_or_else(|| {
+            serde::de::Error::invalid_value(serde::de::Unexpected::Str(&code), &"reorder code")
+        })
+    }
+}
@@ -0,0 +1,303 @@
+// изменение порядка групп символов (kr): первичные веса CLDR идут блоками - специальные группы
+// (пробелы, пунктуация, символы, символы валют, цифры), затем письменности. блоки переставляются
+// целиком, порядок весов внутри блока сохраняется
+//
+// границы групп не записаны в таблицах весов (в allkeys их нет), они известны только для таблиц
+// CLDR (root), см. CollatorData::new. для других таблиц переменные веса определяются по флагу
+// (maxVariable = punct), остальные значения maxVariable и reorder не поддерживаются
+
+use core::ops::RangeInclusive;
+
+use crate::options::{CollatorOptions, MaxVariable, OptionsError, ReorderCode};
+
+/// первый первичный вес группы пробелов - первый переменный вес (меньше - только вес U+FFFE)
+const FIRST_VARIABLE_PRIMARY: u16 = 0x0100;
+/// последний первичный вес группы пробелов (maxVariable = space)
+const LAST_SPACE_PRIMARY: u16 = 0x0108;
+/// последний первичный вес группы пунктуации (maxVariable = punct, веса с флагом is_variable)
+const LAST_PUNCT_PRIMARY: u16 = 0x03D1;
+/// последний первичный вес группы символов (maxVariable = symbol)
+const LAST_SYMBOL_PRIMARY: u16 = 0x1D6E;
+/// последний первичный вес группы символов валют (maxVariable = currency)
+const LAST_CURRENCY_PRIMARY: u16 = 0x1DA6;
+/// последний первичный вес, участвующий в перестановке. далее - вычисляемые веса кодпоинтов без
+/// письменности (FBC0 ..) и специальные веса (FFFD, FFFF)
+const LAST_REORDERABLE_PRIMARY: u16 = 0xFBBF;
+
+/// специальные группы в порядке их первичных весов
+const SPECIAL_GROUPS: [ReorderCode; 5] = [
+    ReorderCode::Space,
+    ReorderCode::Punct,
+    ReorderCode::Symbol,
+    ReorderCode::Currency,
+    ReorderCode::Digit,
+];
+
+/// границы групп первичных весов таблиц
+pub struct PrimaryGroups
+{
+    /// первые первичные веса специальных групп: пробелы, пунктуация, символы, символы валют,
+    /// цифры
+    pub special: [u16; 5],
+    /// письменности: первый первичный вес, код ISO 15924. блок письменности заканчивается перед
+    /// первым весом следующей
+    pub scripts: &'static [(u16, [u8; 4])],
+    /// последний первичный вес, участвующий в перестановке
+    pub last_reorderable: u16,
+}
+
+/// группы первичных весов таблиц CLDR (root)
+pub const CLDR_UND_GROUPS: PrimaryGroups = PrimaryGroups {
+    special: [
+        FIRST_VARIABLE_PRIMARY,
+        LAST_SPACE_PRIMARY + 1,
+        LAST_PUNCT_PRIMARY + 1,
+        LAST_SYMBOL_PRIMARY + 1,
+        LAST_CURRENCY_PRIMARY + 1,
+    ],
+    scripts: &SCRIPT_GROUPS,
+    last_reorderable: LAST_REORDERABLE_PRIMARY,
+};
+
+/// письменности в порядке CLDR root: первый первичный вес, код ISO 15924. блок письменности
+/// заканчивается перед первым весом следующей
+#[rustfmt::skip]
+const SCRIPT_GROUPS: [(u16, [u8; 4]); 157] = [
+    (0x20A9, *b"Latn"), (0x2447, *b"Grek"), (0x2474, *b"Copt"), (0x24B0, *b"Cyrl"),
+    (0x2673, *b"Glag"), (0x26A3, *b"Perm"), (0x26C9, *b"Geor"), (0x271F, *b"Armn"),
+    (0x2748, *b"Hebr"), (0x275E, *b"Phnx"), (0x2774, *b"Samr"), (0x278E, *b"Arab"),
+    (0x287A, *b"Syrc"), (0x28A0, *b"Mand"), (0x28B9, *b"Thaa"), (0x28EB, *b"Nkoo"),
+    (0x290B, *b"Tfng"), (0x2944, *b"Ethi"), (0x2B25, *b"Deva"), (0x2B8B, *b"Beng"),
+    (0x2BCD, *b"Guru"), (0x2C06, *b"Gujr"), (0x2C4B, *b"Orya"), (0x2C8E, *b"Taml"),
+    (0x2CC0, *b"Telu"), (0x2D0A, *b"Knda"), (0x2D54, *b"Mlym"), (0x2D9E, *b"Sinh"),
+    (0x2DEB, *b"Mtei"), (0x2E2A, *b"Sylo"), (0x2E52, *b"Saur"), (0x2E95, *b"Kthi"),
+    (0x2ECA, *b"Mahj"), (0x2EEE, *b"Shrd"), (0x2F33, *b"Khoj"), (0x2F6A, *b"Sind"),
+    (0x2FA3, *b"Mult"), (0x2FC8, *b"Gran"), (0x300C, *b"Newa"), (0x3056, *b"Tirh"),
+    (0x3099, *b"Sidd"), (0x30D4, *b"Modi"), (0x3113, *b"Takr"), (0x3149, *b"Diak"),
+    (0x3181, *b"Nand"), (0x31BF, *b"Dogr"), (0x31F7, *b"Ahom"), (0x3225, *b"Gonm"),
+    (0x3260, *b"Gong"), (0x3293, *b"Sund"), (0x32C3, *b"Brah"), (0x330E, *b"Khar"),
+    (0x333A, *b"Bhks"), (0x3376, *b"Thai"), (0x33B6, *b"Laoo"), (0x33F5, *b"Tavt"),
+    (0x3438, *b"Tibt"), (0x3498, *b"Zanb"), (0x34CD, *b"Soyo"), (0x3505, *b"Marc"),
+    (0x3545, *b"Lepc"), (0x357F, *b"Phag"), (0x35B3, *b"Limb"), (0x35E5, *b"Tglg"),
+    (0x35FC, *b"Hano"), (0x3611, *b"Buhd"), (0x3625, *b"Tagb"), (0x3637, *b"Bugi"),
+    (0x3653, *b"Maka"), (0x366A, *b"Batk"), (0x3689, *b"Rjng"), (0x36AD, *b"Kali"),
+    (0x36CE, *b"Mymr"), (0x377D, *b"Rohg"), (0x37A1, *b"Cakm"), (0x37D7, *b"Khmr"),
+    (0x381D, *b"Tale"), (0x3840, *b"Talu"), (0x3886, *b"Lana"), (0x38D3, *b"Cham"),
+    (0x3918, *b"Bali"), (0x395E, *b"Java"), (0x3998, *b"Kawi"), (0x39D4, *b"Mong"),
+    (0x3A58, *b"Olck"), (0x3A7C, *b"Cher"), (0x3AD2, *b"Osge"), (0x3AF6, *b"Cans"),
+    (0x3DC9, *b"Ogam"), (0x3DE3, *b"Runr"), (0x3E11, *b"Hung"), (0x3E3A, *b"Orkh"),
+    (0x3E67, *b"Vaii"), (0x3F74, *b"Bamu"), (0x41FD, *b"Bass"), (0x421B, *b"Mend"),
+    (0x42E0, *b"Medf"), (0x4300, *b"Adlm"), (0x4323, *b"Hang"), (0x4488, *b"Hrkt"),
+    (0x45DA, *b"Bopo"), (0x4612, *b"Yiii"), (0x4A9F, *b"Lisu"), (0x4ACE, *b"Plrd"),
+    (0x4B5F, *b"Wara"), (0x4B80, *b"Pauc"), (0x4BB9, *b"Hmng"), (0x4C13, *b"Hmnp"),
+    (0x4C46, *b"Toto"), (0x4C64, *b"Wcho"), (0x4C90, *b"Nagm"), (0x4CAC, *b"Lyci"),
+    (0x4CC9, *b"Cari"), (0x4CFA, *b"Lydi"), (0x4D14, *b"Ital"), (0x4D37, *b"Goth"),
+    (0x4D52, *b"Dsrt"), (0x4D7A, *b"Shaw"), (0x4DAA, *b"Dupl"), (0x4E35, *b"Osma"),
+    (0x4E53, *b"Elba"), (0x4E7B, *b"Aghb"), (0x4EAF, *b"Vith"), (0x4ED2, *b"Sora"),
+    (0x4EEB, *b"Mroo"), (0x4F0A, *b"Tnsa"), (0x4F59, *b"Linb"), (0x502C, *b"Lina"),
+    (0x5181, *b"Cprt"), (0x51B8, *b"Cpmn"), (0x5219, *b"Sarb"), (0x5236, *b"Narb"),
+    (0x5253, *b"Avst"), (0x5288, *b"Palm"), (0x529E, *b"Nbat"), (0x52B4, *b"Hatr"),
+    (0x52C9, *b"Armi"), (0x52DF, *b"Prti"), (0x52F5, *b"Phli"), (0x5308, *b"Phlp"),
+    (0x531A, *b"Elym"), (0x5330, *b"Mani"), (0x5354, *b"Sogo"), (0x5367, *b"Sogd"),
+    (0x537C, *b"Ougr"), (0x538E, *b"Yezi"), (0x53BA, *b"Chrs"), (0x53CF, *b"Ugar"),
+    (0x53ED, *b"Xpeo"), (0x5419, *b"Xsux"), (0x5877, *b"Egyp"), (0x5CAD, *b"Mero"),
+    (0x5CC8, *b"Hluw"), (0xFB00, *b"Tang"), (0xFB01, *b"Nshu"), (0xFB02, *b"Kits"),
+    (0xFB40, *b"Hani"),
+];
+
+/// коды письменностей, веса которых находятся в блоке другой письменности
+const SCRIPT_ALIASES: [([u8; 4], [u8; 4]); 5] = [
+    (*b"Hira", *b"Hrkt"),
+    (*b"Kana", *b"Hrkt"),
+    (*b"Hans", *b"Hani"),
+    (*b"Hant", *b"Hani"),
+    (*b"Merc", *b"Mero"),
+];
+
+/// известна ли письменность
+pub fn is_known_script(code: ReorderCode) -> bool
+{
+    match code {
+        ReorderCode::Script(script) => script_group(script, CLDR_UND_GROUPS.scripts).is_some(),
+        _ => true,
+    }
+}
+
+/// перестановка блоков первичных весов и переменные веса - зависят от групп первичных весов
+#[derive(Debug, Default, Clone)]
+pub struct Reordering
+{
+    /// блоки в исходном порядке: первый вес, последний вес, новый первый вес.
+    /// пустой список - порядок не меняется
+    ranges: Vec<(u16, u16, u16)>,
+    /// переменные первичные веса (maxVariable), None - переменные веса отмечены флагом
+    variable_primaries: Option<RangeInclusive<u16>>,
+}
+
+impl Reordering
+{
+    /// перестановка и переменные веса, заданные опциями, для таблиц с группами первичных весов
+    /// groups (None - границы групп неизвестны)
+    pub fn new(
+        options: &CollatorOptions,
+        groups: Option<&PrimaryGroups>,
+    ) -> Result<Self, OptionsError>
+    {
+        let variable_primaries = match options.max_variable {
+            MaxVariable::Punct => None,
+            max_variable => {
+                let groups = groups.ok_or(OptionsError::MaxVariable(max_variable))?;
+
+                Some(groups.special[0] ..= groups.special[max_variable as usize + 1] - 1)
+            }
+        };
+
+        let ranges = match (options.reorder.is_empty(), groups) {
+            (true, _) => vec![],
+            (false, Some(groups)) => reorder_ranges(&options.reorder, groups),
+            (false, None) => return Err(OptionsError::Reorder),
+        };
+
+        Ok(Self {
+            ranges,
+            variable_primaries,
+        })
+    }
+
+    /// переменные первичные веса, None - переменные веса отмечены флагом (maxVariable = punct)
+    #[inline(always)]
+    pub fn variable_primaries(&self) -> Option<&RangeInclusive<u16>>
+    {
+        self.variable_primaries.as_ref()
+    }
+
+    /// первичный вес после перестановки
+    #[inline(always)]
+    pub fn remap(&self, primary: u16) -> u16
+    {
+        let (Some(&(first, ..)), Some(&(_, last, _))) = (self.ranges.first(), self.ranges.last())
+        else {
+            return primary;
+        };
+
+        if !(first ..= last).contains(&primary) {
+            return primary;
+        }
+
+        let index = match self
+            .ranges
+            .binary_search_by_key(&primary, |&(first, ..)| first)
+        {
+            Ok(index) => index,
+            Err(index) => index - 1,
+        };
+
+        let (first, _, new_first) = self.ranges[index];
+
+        primary - first + new_first
+    }
+}
+
+/// блоки перестановки, заданной списком групп (UTS #35, kr), пустой список - порядок не меняется
+///
+/// специальные группы, не указанные в списке, остаются в начале в исходном порядке. за ними
+/// следуют группы, указанные до Others, затем - не указанные письменности, затем - группы,
+/// указанные после Others. неизвестные и повторяющиеся коды пропускаются
+fn reorder_ranges(codes: &[ReorderCode], primary_groups: &PrimaryGroups) -> Vec<(u16, u16, u16)>
+{
+    // блоки всех групп в исходном порядке
+    let groups: Vec<(ReorderCode, u16, u16)> = SPECIAL_GROUPS
+        .iter()
+        .zip(primary_groups.special)
+        .map(|(&code, first)| (code, first))
+        .chain(
+            primary_groups
+                .scripts
+                .iter()
+                .map(|&(first, script)| (ReorderCode::Script(script), first)),
+        )
+        .chain(core::iter::once((
+            ReorderCode::Others,
+            primary_groups.last_reorderable + 1,
+        )))
+        .collect::<Vec<_>>()
+        .windows(2)
+        .map(|pair| (pair[0].0, pair[0].1, pair[1].1 - 1))
+        .collect();
+
+    let mut head = vec![];
+    let mut tail = vec![];
+    let mut after_others = false;
+
+    for &code in codes {
+        let code = match code {
+            ReorderCode::Others => {
+                after_others = true;
+                continue;
+            }
+            ReorderCode::Script(script) => match script_group(script, primary_groups.scripts) {
+                Some(script) => ReorderCode::Script(script),
+                None => continue,
+            },
+            code => code,
+        };
+
+        if head.contains(&code) || tail.contains(&code) {
+            continue;
+        }
+
+        match after_others {
+            true => tail.push(code),
+            false => head.push(code),
+        }
+    }
+
+    let is_listed = |code: &ReorderCode| head.contains(code) || tail.contains(code);
+
+    let order = groups
+        .iter()
+        .filter(|(code, ..)| !matches!(code, ReorderCode::Script(_)) && !is_listed(code))
+        .map(|(code, ..)| *code)
+        .chain(head.iter().copied())
+        .chain(
+            groups
+                .iter()
+                .filter(|(code, ..)| matches!(code, ReorderCode::Script(_)) && !is_listed(code))
+                .map(|(code, ..)| *code),
+        )
+        .chain(tail.iter().copied());
+
+    let mut ranges = Vec::with_capacity(groups.len());
+    let mut next = primary_groups.special[0];
+
+    for code in order {
+        let &(_, first, last) = groups.iter().find(|(group, ..)| *group == code).unwrap();
+
+        ranges.push((first, last, next));
+        next += last - first + 1;
+    }
+
+    ranges.sort_unstable_by_key(|&(first, ..)| first);
+
+    match ranges
+        .iter()
+        .all(|&(first, _, new_first)| first == new_first)
+    {
+        true => vec![],
+        false => ranges,
+    }
+}
+
+/// блок письменности (с учётом кодов, веса которых находятся в блоке другой письменности)
+fn script_group(script: [u8; 4], scripts: &[(u16, [u8; 4])]) -> Option<[u8; 4]>
+{
+    let script = SCRIPT_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == script)
+        .map_or(script, |&(_, group)| group);
+
+    scripts
+        .iter()
+        .any(|&(_, code)| code == script)
+        .then_some(script)
+}
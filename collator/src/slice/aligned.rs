@@ -11,6 +11,29 @@ use core::ptr::copy_nonoverlapping;
 pub struct Aligned<'a, T>
 {
     data: &'a [T],
+    /// память выделена при создании и освобождается при удалении
+    owned: bool,
+}
+
+/// массив, выровненный при компиляции - для статических таблиц
+#[repr(C, align(16))]
+pub struct AlignedArray<T, const N: usize>(pub [T; N]);
+
+impl<T: Copy, const N: usize> AlignedArray<T, N>
+{
+    /// скопировать слайс длины N в выровненный массив (при компиляции)
+    pub const fn from_slice(source: &[T], zero: T) -> Self
+    {
+        let mut data = [zero; N];
+        let mut i = 0;
+
+        while i < N {
+            data[i] = source[i];
+            i += 1;
+        }
+
+        Self(data)
+    }
 }
 
 impl<'a, T> Aligned<'a, T>
@@ -21,6 +44,11 @@ impl<'a, T> Aligned<'a, T>
     {
         let len = source.len();
 
+        // выделение памяти нулевого размера недопустимо
+        if len == 0 {
+            return Self::borrowed(&[]);
+        }
+
         unsafe {
             let data = alloc(Self::layout(len)) as *mut T;
 
@@ -28,10 +56,17 @@ impl<'a, T> Aligned<'a, T>
 
             Self {
                 data: core::slice::from_raw_parts(data, len),
+                owned: true,
             }
         }
     }
 
+    /// уже выровненные данные (например, AlignedArray) - без копирования
+    pub const fn borrowed(data: &'a [T]) -> Self
+    {
+        Self { data, owned: false }
+    }
+
     fn layout(length: usize) -> Layout
     {
         if size_of::<T>() >= 4 {
@@ -39,7 +74,7 @@ impl<'a, T> Aligned<'a, T>
         }
 
         let size = size_of::<T>() * length;
-        let length = (size + 7) / 8;
+        let length = size.div_ceil(8);
 
         Layout::array::<u64>(length).unwrap()
     }
@@ -61,6 +96,10 @@ impl<'a, T> Drop for Aligned<'a, T>
     #[inline(never)]
     fn drop(&mut self)
     {
+        if !self.owned {
+            return;
+        }
+
         unsafe {
             let layout = Self::layout(self.data.len());
             dealloc(self.data.as_ptr() as *mut u8, layout)
@@ -0,0 +1,44 @@
+use crate::ce::CollationElement;
+use crate::codepoint::CodepointsIter;
+use crate::options::CollatorOptions;
+
+/// состояние получения весов строки: позволяет получать веса порциями, продолжая с места остановки
+pub struct WeightsStream<'a>
+{
+    /// итератор по кодпоинтам строки
+    pub codepoints: CodepointsIter<'a>,
+    /// буфер элементов сопоставления, ожидающих записи
+    pub buffer: Vec<CollationElement>,
+    /// CCC предыдущего кодпоинта, 0xFF - требуется декомпозиция
+    pub previous_ccc: u8,
+    /// строка обработана полностью
+    pub is_finished: bool,
+    /// последовательности цифр заменяются весами числа (numeric)
+    pub numeric: bool,
+    /// первичный вес цифры 0 - начало весов числа (numeric)
+    pub numeric_primary: u16,
+    /// порядок нестартеров проверяется (normalization)
+    pub normalization: bool,
+}
+
+impl<'a> WeightsStream<'a>
+{
+    /// новое состояние для итератора по кодпоинтам
+    #[inline(always)]
+    pub fn new(
+        codepoints: CodepointsIter<'a>,
+        options: &CollatorOptions,
+        numeric_primary: u16,
+    ) -> Self
+    {
+        Self {
+            codepoints,
+            buffer: vec![],
+            previous_ccc: 0,
+            is_finished: false,
+            numeric: options.numeric,
+            numeric_primary,
+            normalization: options.normalization,
+        }
+    }
+}
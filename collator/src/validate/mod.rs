@@ -0,0 +1,252 @@
+// проверка таблиц весов, полученных извне (см. Collator::try_from_baked): коллатор читает таблицы
+// без части проверок, поэтому некорректные или несовместимые данные должны быть отклонены заранее
+
+use core::fmt;
+
+use crate::codepoint::{CodepointWithData, CodepointsIter};
+use crate::data::WeightsData;
+use crate::options::OptionsError;
+use crate::{
+    MARKER_CCC_HANGUL, MARKER_IMPLICIT, MARKER_NONSTARTER_SINGLE_WEIGHTS, MARKER_NONSTARTER_TRIE,
+    MARKER_STARTER_DECOMPOSITION, MARKER_STARTER_EXPANSION, MARKER_STARTER_SINGLE_WEIGHTS,
+    MARKER_STARTER_TRIE,
+};
+
+/// последний кодпоинт, который может быть записан в непрерывных блоках индекса
+const LAST_CONTINUOUS_CODEPOINT: u32 = 0xFFF;
+/// позиции в таблицах расширений и бора - u16
+const MAX_TABLE_LEN: usize = 0x10000;
+/// слоги хангыль
+const HANGUL_SYLLABLES: core::ops::RangeInclusive<u32> = 0xAC00 ..= 0xD7A3;
+
+/// ошибка проверки таблиц весов
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataError
+{
+    /// continuous_block_end за пределами непрерывной части индекса
+    InvalidContinuousBlockEnd(u32),
+    /// таблица расширений или бора длиннее, чем позволяют адресовать записи
+    TableTooLarge(usize),
+    /// запись о кодпоинте ссылается за пределы index / scalars32 / scalars64
+    IndexOutOfBounds(u32),
+    /// неизвестный маркер записи о кодпоинте
+    InvalidMarker
+    {
+        code: u32, marker: u8
+    },
+    /// веса расширения кодпоинта за пределами expansions или адресуемой (u16) части таблицы
+    ExpansionOutOfBounds(u32),
+    /// декомпозиция кодпоинта некорректна (маркер хангыль у другого кодпоинта, меньше двух
+    /// элементов)
+    InvalidDecomposition(u32),
+    /// нарушена структура бора: узел или веса за пределами tries, узел последовательности без
+    /// потомков
+    InvalidTrie(usize),
+    /// пустая таблица index, scalars32 или scalars64
+    EmptyTable(&'static str),
+    /// нестартер (запись о кодпоинте или узел его последовательности) с CCC = 0
+    InvalidNonstarter(u32),
+    /// таблицы корректны, но не поддерживают опции коллатора (см. Collator::try_from_baked)
+    UnsupportedOptions(OptionsError),
+}
+
+impl fmt::Display for DataError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            Self::InvalidContinuousBlockEnd(code) => {
+                write!(f, "некорректное значение continuous_block_end: {:#X}", code)
+            }
+            Self::TableTooLarge(len) => write!(f, "слишком большая таблица: {} элементов", len),
+            Self::IndexOutOfBounds(code) => {
+                write!(f, "запись о кодпоинте U+{:04X} за пределами таблиц", code)
+            }
+            Self::InvalidMarker { code, marker } => {
+                write!(
+                    f,
+                    "неизвестный маркер {} у кодпоинта U+{:04X}",
+                    marker, code
+                )
+            }
+            Self::ExpansionOutOfBounds(code) => {
+                write!(
+                    f,
+                    "расширение кодпоинта U+{:04X} за пределами таблицы",
+                    code
+                )
+            }
+            Self::InvalidDecomposition(code) => {
+                write!(f, "некорректная декомпозиция кодпоинта U+{:04X}", code)
+            }
+            Self::InvalidTrie(pos) => write!(f, "некорректный узел бора в позиции {}", pos),
+            Self::EmptyTable(table) => write!(f, "пустая таблица: {}", table),
+            Self::InvalidNonstarter(code) => {
+                write!(f, "нестартер U+{:04X} с нулевым CCC", code)
+            }
+            Self::UnsupportedOptions(error) => write!(f, "опции не поддерживаются: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for DataError {}
+
+/// проверить таблицы весов
+pub fn validate(data: &WeightsData) -> Result<(), DataError>
+{
+    if data.continuous_block_end > LAST_CONTINUOUS_CODEPOINT {
+        return Err(DataError::InvalidContinuousBlockEnd(
+            data.continuous_block_end,
+        ));
+    }
+
+    for (table, len) in [
+        ("index", data.index.len()),
+        ("scalars32", data.scalars32.len()),
+        ("scalars64", data.scalars64.len()),
+    ] {
+        if len == 0 {
+            return Err(DataError::EmptyTable(table));
+        }
+    }
+
+    for len in [data.expansions.len(), data.tries.len()] {
+        if len > MAX_TABLE_LEN {
+            return Err(DataError::TableTooLarge(len));
+        }
+    }
+
+    let codepoints = CodepointsIter::new(
+        "",
+        data.scalars64,
+        data.scalars32,
+        data.index,
+        data.continuous_block_end,
+        false,
+    );
+
+    for code in (0 ..= 0x10FFFF).filter(|code| char::from_u32(*code).is_some()) {
+        let codepoint = CodepointWithData {
+            data: codepoints
+                .checked_data_value(code)
+                .ok_or(DataError::IndexOutOfBounds(code))?,
+            code,
+        };
+
+        validate_codepoint(&codepoint, data)?;
+    }
+
+    Ok(())
+}
+
+/// проверить запись о кодпоинте и связанные с ней данные
+fn validate_codepoint(codepoint: &CodepointWithData, data: &WeightsData) -> Result<(), DataError>
+{
+    let code = codepoint.code;
+    let pos = codepoint.data_pos() as usize;
+
+    match codepoint.marker() {
+        MARKER_IMPLICIT | MARKER_STARTER_SINGLE_WEIGHTS => (),
+        MARKER_NONSTARTER_SINGLE_WEIGHTS => {
+            if codepoint.single_weights_ccc() == 0 {
+                return Err(DataError::InvalidNonstarter(code));
+            }
+        }
+        MARKER_STARTER_EXPANSION => {
+            // конец расширения вычисляется в u16 (см. CodepointWithData::expansion_weights)
+            let end = pos + codepoint.ccc_or_len() as usize;
+
+            if end > data.expansions.len() || end > u16::MAX as usize {
+                return Err(DataError::ExpansionOutOfBounds(code));
+            }
+        }
+        MARKER_STARTER_DECOMPOSITION => {
+            if codepoint.ccc_or_len() == MARKER_CCC_HANGUL {
+                return match HANGUL_SYLLABLES.contains(&code) {
+                    true => Ok(()),
+                    false => Err(DataError::InvalidDecomposition(code)),
+                };
+            }
+
+            // узел с весами, за ним - список элементов декомпозиции: стартер и нестартеры
+            let decomposition = validate_node(data.tries, pos)?;
+
+            if validate_list(data.tries, decomposition, false)? < 2
+                || node_ccc(data.tries[decomposition]) != 0
+            {
+                return Err(DataError::InvalidDecomposition(code));
+            }
+        }
+        MARKER_STARTER_TRIE => {
+            let children = validate_node(data.tries, pos)?;
+
+            if data.tries[pos] & 1 == 0 {
+                return Err(DataError::InvalidTrie(pos));
+            }
+
+            validate_list(data.tries, children, false)?;
+        }
+        MARKER_NONSTARTER_TRIE => {
+            validate_list(data.tries, pos, true)?;
+        }
+        marker => return Err(DataError::InvalidMarker { code, marker }),
+    }
+
+    Ok(())
+}
+
+/// проверить узел бора, возвращает позицию следующей записи
+fn validate_node(tries: &[u32], pos: usize) -> Result<usize, DataError>
+{
+    let value = *tries.get(pos).ok_or(DataError::InvalidTrie(pos))?;
+    let next = pos + 1 + (value >> 26) as usize;
+
+    match next <= tries.len() && next <= u16::MAX as usize {
+        true => Ok(next),
+        false => Err(DataError::InvalidTrie(pos)),
+    }
+}
+
+/// проверить список узлов бора вместе с поддеревьями (см. TrieIter), возвращает количество узлов
+/// верхнего уровня. nonstarters - все узлы должны быть нестартерами (последовательности,
+/// начинающиеся с нестартера)
+fn validate_list(tries: &[u32], start: usize, nonstarters: bool) -> Result<usize, DataError>
+{
+    // открытые списки потомков, последний элемент которых ещё не прочитан
+    let mut open = 0;
+    let mut is_top_open = true;
+    let mut count = 0;
+    let mut pos = start;
+
+    while is_top_open || open != 0 {
+        let value = *tries.get(pos).ok_or(DataError::InvalidTrie(pos))?;
+        let is_last = value & 2 != 0;
+
+        if nonstarters && node_ccc(value) == 0 {
+            return Err(DataError::InvalidNonstarter((value >> 8) & 0x3FFFF));
+        }
+
+        pos = validate_node(tries, pos)?;
+
+        match open {
+            0 => {
+                count += 1;
+                is_top_open = !is_last;
+            }
+            _ => open -= is_last as usize,
+        }
+
+        // за узлом с потомками следует список потомков
+        if value & 1 != 0 {
+            open += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+/// CCC узла бора (см. TrieNode)
+fn node_ccc(value: u32) -> u8
+{
+    (value >> 2) as u8 & 0x3F
+}
@@ -1,11 +1,22 @@
 use core::fmt::Debug;
 
 /// веса для кодпоинта, 3 уровня
+#[repr(transparent)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Weights(u32);
 
 impl Weights
 {
+    /// слайс u32-весов как слайс весов
+    #[inline(always)]
+    pub fn from_slice(weights: &[u32]) -> &[Self]
+    {
+        // Weights - repr(transparent) над u32
+        unsafe { core::slice::from_raw_parts(weights.as_ptr() as *const Self, weights.len()) }
+    }
+
     /// сжатое значение
     #[inline(always)]
     pub fn value(&self) -> u32
@@ -59,6 +70,15 @@ impl Weights
     }
 }
 
+/// третичный вес прописного варианта символа (см. таблицу третичных весов в UTS #10):
+/// 0x08 - прописная, 0x09 - <wide>, 0x0A - <compat>, 0x0B - <font>, 0x0C - <circle>,
+/// 0x1D - <super>, <sub>
+#[inline(always)]
+pub fn is_uppercase_l3(l3: u16) -> bool
+{
+    matches!(l3, 0x08 ..= 0x0C | 0x1D)
+}
+
 impl From<u32> for Weights
 {
     fn from(value: u32) -> Self
@@ -0,0 +1,73 @@
+use core::cmp::Ordering;
+
+use unicode_collator::{
+    key::compare_keys,
+    options::{AlternateHandling, CollatorOptions, Strength},
+    Collator,
+};
+
+/// сравнение строк при заданном типе сравнения переменных весов - с ключами и без
+fn compare(alternate: AlternateHandling, a: &str, b: &str) -> Ordering
+{
+    let collator = Collator::new(CollatorOptions {
+        strength: Strength::Quaternary,
+        alternate,
+        ..Default::default()
+    });
+
+    let ordering = collator.compare(a, b);
+
+    assert_eq!(
+        compare_keys(&collator.get_key(a).weights, &collator.get_key(b).weights),
+        ordering,
+        "{:?} {} {}",
+        alternate,
+        a,
+        b
+    );
+
+    ordering
+}
+
+/// Blanked: переменные веса и следующие за ними игнорируемые не учитываются, L4 отсутствует
+#[test]
+fn test_blanked()
+{
+    let alternate = AlternateHandling::Blanked;
+
+    assert_eq!(compare(alternate, "ab", "a b"), Ordering::Equal);
+    assert_eq!(compare(alternate, "a b", "a-b"), Ordering::Equal);
+    assert_eq!(compare(alternate, "ab", "a-\u{301}b"), Ordering::Equal);
+    assert_eq!(compare(alternate, "ab", "a\u{301}b"), Ordering::Less);
+    assert_eq!(compare(alternate, "ab", "Ab"), Ordering::Less);
+
+    let collator = Collator::new(CollatorOptions {
+        strength: Strength::Quaternary,
+        alternate,
+        ..Default::default()
+    });
+
+    assert_eq!(collator.get_key("a b").levels_count(), 3);
+}
+
+/// Shift-Trimmed: конечные веса FFFF четвертичного уровня отбрасываются
+#[test]
+fn test_shift_trimmed()
+{
+    assert_eq!(
+        compare(AlternateHandling::Shifted, "a b", "ab"),
+        Ordering::Less
+    );
+    assert_eq!(
+        compare(AlternateHandling::ShiftTrimmed, "a b", "ab"),
+        Ordering::Greater
+    );
+    assert_eq!(
+        compare(AlternateHandling::ShiftTrimmed, "a b", "a-b"),
+        Ordering::Less
+    );
+    assert_eq!(
+        compare(AlternateHandling::ShiftTrimmed, "ab", "ab "),
+        Ordering::Less
+    );
+}
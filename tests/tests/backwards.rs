@@ -0,0 +1,37 @@
+use unicode_collator::{key::compare_keys, options::CollatorOptions, Collator};
+
+/// французский порядок диакритики: вторичные веса сравниваются с конца строки
+#[test]
+fn test_backwards_secondary()
+{
+    for (backwards_secondary, expected) in [
+        (false, ["cote", "coté", "côte", "côté"]),
+        (true, ["cote", "côte", "coté", "côté"]),
+    ] {
+        let collator = Collator::new(CollatorOptions {
+            backwards_secondary,
+            ..Default::default()
+        });
+
+        let mut strings = ["côté", "coté", "côte", "cote"];
+
+        strings.sort_by(|a, b| collator.compare(a, b));
+        assert_eq!(strings, expected);
+
+        strings.sort_by(|a, b| {
+            compare_keys(&collator.get_key(a).weights, &collator.get_key(b).weights)
+        });
+        assert_eq!(strings, expected);
+
+        let mut parts = vec![];
+        let mut buffer = [0; 3];
+        let mut sort_key_parts = collator.sort_key_parts("côté");
+
+        while !sort_key_parts.is_finished() {
+            let written = sort_key_parts.next_part(&mut buffer);
+            parts.extend_from_slice(&buffer[.. written]);
+        }
+
+        assert_eq!(parts, collator.get_key("côté").to_bytes());
+    }
+}
@@ -0,0 +1,130 @@
+use unicode_collator::options::{
+    AlternateHandling, Bcp47Error, CaseFirst, CollatorOptions, MaxVariable, ReorderCode, Strength,
+};
+
+/// разбор расширения -u- тега BCP 47
+#[test]
+fn test_from_bcp47()
+{
+    let options = CollatorOptions::from_bcp47(
+        "de-u-co-standard-ks-level2-ka-shifted-kb-kc-true-kf-upper-kk-false-kn-kr-cyrl-digit-kv-space",
+    )
+    .unwrap();
+
+    assert_eq!(options.strength, Strength::Secondary);
+    assert_eq!(options.alternate, AlternateHandling::Shifted);
+    assert!(options.backwards_secondary);
+    assert!(options.case_level);
+    assert_eq!(options.case_first, CaseFirst::Upper);
+    assert!(!options.normalization);
+    assert!(options.numeric);
+    assert_eq!(
+        options.reorder,
+        [ReorderCode::Script(*b"Cyrl"), ReorderCode::Digit]
+    );
+    assert_eq!(options.max_variable, MaxVariable::Space);
+
+    // без расширения, регистр, прочие ключи и расширения
+    let options = CollatorOptions::from_bcp47("ru").unwrap();
+    assert_eq!(options.strength, Strength::Tetriary);
+    assert!(options.normalization);
+
+    let options =
+        CollatorOptions::from_bcp47("EN_US-u-attr-CA-gregory-KS-Identic-kn-false-x-ks-level1")
+            .unwrap();
+    assert_eq!(options.strength, Strength::Identical);
+    assert!(!options.numeric);
+
+    // -u- после x - часть подтегов частного использования
+    let options = CollatorOptions::from_bcp47("en-x-u-kn").unwrap();
+    assert!(!options.numeric);
+
+    let options = CollatorOptions::from_bcp47("en-x-priv-u-ks-level1").unwrap();
+    assert_eq!(options.strength, Strength::Tetriary);
+}
+
+/// недопустимые и неподдерживаемые значения
+#[test]
+fn test_from_bcp47_errors()
+{
+    let invalid = |key: &str, value: &str| Bcp47Error::InvalidValue {
+        key: key.to_owned(),
+        value: value.to_owned(),
+    };
+
+    for (tag, error) in [
+        ("de-u-ks-level5", invalid("ks", "level5")),
+        ("de-u-ka-blanked", invalid("ka", "blanked")),
+        ("de-u-kn-yes", invalid("kn", "yes")),
+        ("de-u-kf-upper-lower", invalid("kf", "upper-lower")),
+        ("de-u-kr-latn-abcd", invalid("kr", "latn-abcd")),
+        ("de-u-kr", invalid("kr", "")),
+        (
+            "de-u-co-phonebk",
+            Bcp47Error::UnsupportedValue {
+                key: "co".to_owned(),
+                value: "phonebk".to_owned(),
+            },
+        ),
+        ("de-u-kh-true", Bcp47Error::UnsupportedKey("kh".to_owned())),
+        (
+            "de-u-ks-level1-ks-level2",
+            Bcp47Error::DuplicateKey("ks".to_owned()),
+        ),
+        (
+            "de--u-ks-level1",
+            Bcp47Error::InvalidTag("de--u-ks-level1".to_owned()),
+        ),
+    ] {
+        assert_eq!(
+            CollatorOptions::from_bcp47(tag).unwrap_err(),
+            error,
+            "{}",
+            tag
+        );
+    }
+}
+
+/// запись опций в виде тега и обратный разбор
+#[test]
+fn test_to_bcp47()
+{
+    assert_eq!(CollatorOptions::default().to_bcp47().unwrap(), "und");
+
+    let options = CollatorOptions {
+        strength: Strength::Quaternary,
+        alternate: AlternateHandling::Shifted,
+        max_variable: MaxVariable::Currency,
+        case_first: CaseFirst::Lower,
+        case_level: true,
+        backwards_secondary: true,
+        numeric: true,
+        reorder: vec![ReorderCode::Others, ReorderCode::Script(*b"Grek")],
+        normalization: false,
+        ..Default::default()
+    };
+
+    let tag = options.to_bcp47().unwrap();
+
+    assert_eq!(
+        tag,
+        "und-u-ka-shifted-kb-kc-kf-lower-kk-false-kn-kr-others-grek-ks-level4-kv-currency"
+    );
+    assert_eq!(
+        CollatorOptions::from_bcp47(&tag)
+            .unwrap()
+            .to_bcp47()
+            .unwrap(),
+        tag
+    );
+
+    let options = CollatorOptions {
+        alternate: AlternateHandling::Blanked,
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        options.to_bcp47(),
+        Err(Bcp47Error::UnsupportedValue { .. })
+    ));
+}
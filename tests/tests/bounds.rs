@@ -0,0 +1,72 @@
+use unicode_collator::{
+    key::{BoundType, KeyFormat},
+    options::{CollatorOptions, Strength},
+    Collator,
+};
+
+const NAMES: [&str; 12] = [
+    "m", "ma", "Mu", "mü", "Mü", "MÜ", "mü-", "Mull", "Müller", "MÜNCHEN", "mv", "Nü",
+];
+
+/// строки из NAMES, попадающие в диапазон границ префикса
+fn range(prefix: &str, strength: Strength, upper: BoundType, format: KeyFormat) -> Vec<&str>
+{
+    let collator = Collator::new(CollatorOptions::default());
+    let key = collator.get_key(prefix);
+
+    let lower = key.bound(strength, BoundType::Lower).encode(format);
+    let upper = key.bound(strength, upper).encode(format);
+
+    NAMES
+        .iter()
+        .filter(|name| {
+            let key = collator.get_sort_key_bytes(name, format);
+            lower <= key && key <= upper
+        })
+        .copied()
+        .collect()
+}
+
+#[test]
+fn test_prefix_bounds()
+{
+    for format in [KeyFormat::Uncompressed, KeyFormat::Compressed] {
+        assert_eq!(
+            range("mü", Strength::Primary, BoundType::UpperLong, format),
+            ["Mu", "mü", "Mü", "MÜ", "mü-", "Mull", "Müller", "MÜNCHEN"]
+        );
+        assert_eq!(
+            range("mü", Strength::Primary, BoundType::Upper, format),
+            ["Mu", "mü", "Mü", "MÜ"]
+        );
+        assert_eq!(
+            range("mü", Strength::Secondary, BoundType::Upper, format),
+            ["mü", "Mü", "MÜ"]
+        );
+        assert_eq!(
+            range("mü", Strength::Tetriary, BoundType::Upper, format),
+            ["mü"]
+        );
+    }
+}
+
+/// UpperLong при силе больше первичной: первичные веса совпадают полностью, продолжения префикса
+/// ("Müller", "mü-") в диапазон не попадают
+#[test]
+fn test_prefix_bounds_levels()
+{
+    for format in [KeyFormat::Uncompressed, KeyFormat::Compressed] {
+        assert_eq!(
+            range("mü", Strength::Secondary, BoundType::UpperLong, format),
+            ["mü", "Mü", "MÜ"]
+        );
+        assert_eq!(
+            range("mü", Strength::Tetriary, BoundType::UpperLong, format),
+            ["mü"]
+        );
+        assert_eq!(
+            range("Mü", Strength::Tetriary, BoundType::UpperLong, format),
+            ["Mü"]
+        );
+    }
+}
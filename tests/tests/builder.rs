@@ -0,0 +1,301 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::{env, fs, process};
+
+use unicode_collation_builder::{
+    build, build_cldr_root, compare, BuildError, Tables, ALLKEYS_CLDR, FRACTIONAL_UCA, LDML_DTD,
+};
+use unicode_collator::{
+    cldr_und, implicit_weights, options::CollatorOptions, Collator, CollatorData,
+};
+
+const UNICODE_DATA: &str = "\
+0061;LATIN SMALL LETTER A;Ll;0;L;;;;;N;;;;0041;
+0063;LATIN SMALL LETTER C;Ll;0;L;;;;;N;;;;0043;
+0068;LATIN SMALL LETTER H;Ll;0;L;;;;;N;;;;0048;
+00E1;LATIN SMALL LETTER A WITH ACUTE;Ll;0;L;0061 0301;;;;N;LATIN SMALL LETTER A ACUTE;;00C1;;00C1
+0301;COMBINING ACUTE ACCENT;Mn;230;NSM;;;;;N;NON-SPACING ACUTE;;;;
+0316;COMBINING GRAVE ACCENT BELOW;Mn;220;NSM;;;;;N;NON-SPACING GRAVE BELOW;;;;
+2126;OHM SIGN;Lu;0;L;03A9;;;;N;OHM;;;03C9;
+4E00;<CJK Ideograph, First>;Lo;0;L;;;;;N;;;;;
+9FFF;<CJK Ideograph, Last>;Lo;0;L;;;;;N;;;;;
+F900;CJK COMPATIBILITY IDEOGRAPH-F900;Lo;0;L;8C48;;;;N;;;;;
+FB01;LATIN SMALL LIGATURE FI;Ll;0;L;<compat> 0066 0069;;;;N;;;;;
+";
+
+const ALLKEYS: &str = "\
+@version 15.1.0
+@implicitweights 17000..18AFF; FB00 # Tangut and Tangut Components
+
+0061 ; [.2000.0020.0002] # LATIN SMALL LETTER A
+0063 ; [.2010.0020.0002] # LATIN SMALL LETTER C
+0063 0068 ; [.2015.0020.0002] # LATIN SMALL LETTER C + LATIN SMALL LETTER H
+0068 ; [.2020.0020.0002] # LATIN SMALL LETTER H
+00E1 ; [.2000.0020.0002][.0000.0024.0002] # LATIN SMALL LETTER A WITH ACUTE
+0301 ; [.0000.0024.0002] # COMBINING ACUTE ACCENT
+0316 ; [.0000.0025.0002] # COMBINING GRAVE ACCENT BELOW
+03A9 ; [*0300.0020.0008] # GREEK CAPITAL LETTER OMEGA
+";
+
+/// коллатор с таблицами, собранными из ALLKEYS
+fn collator() -> Collator<'static>
+{
+    let tables = build(ALLKEYS, UNICODE_DATA).unwrap();
+    let data = CollatorData::try_from_baked(tables.as_weights_data()).unwrap();
+
+    Collator::from_data(Arc::new(data), CollatorOptions::default()).unwrap()
+}
+
+/// веса строк, полученные коллатором с собранными таблицами
+#[test]
+fn test_build_weights()
+{
+    let collator = collator();
+
+    let a = 0x2000 | (0x20 << 16) | (0x02 << 25);
+    let acute = (0x24 << 16) | (0x02 << 25);
+    let grave_below = (0x25 << 16) | (0x02 << 25);
+
+    // сокращение
+    assert_eq!(
+        collator.get_weights("cha"),
+        [0x2015 | (0x20 << 16) | (0x02 << 25), a]
+    );
+
+    // кодпоинт с декомпозицией и его NFD, в т.ч. с нестартером, который должен оказаться перед
+    // нестартером декомпозиции
+    assert_eq!(collator.get_weights("\u{E1}"), [a, acute]);
+    assert_eq!(collator.get_weights("a\u{301}"), [a, acute]);
+    assert_eq!(
+        collator.get_weights("\u{E1}\u{316}"),
+        [a, grave_below, acute]
+    );
+
+    // синглтоны, переменный вес
+    assert_eq!(
+        collator.get_weights("\u{2126}"),
+        [0x300 | (0x20 << 16) | (0x08 << 25) | (1 << 30)]
+    );
+    assert_eq!(collator.get_weights("\u{F900}"), implicit_weights(0x8C48));
+
+    // кодпоинты, отсутствующие в allkeys
+    assert_eq!(collator.get_weights("\u{4E00}"), implicit_weights(0x4E00));
+    assert_eq!(collator.get_weights("b"), implicit_weights(0x62));
+}
+
+/// версия, исходный код и двоичный контейнер
+#[test]
+fn test_build_output()
+{
+    let tables = build(ALLKEYS, UNICODE_DATA).unwrap();
+
+    assert_eq!(tables.version.unicode, [15, 1, 0]);
+
+    let source = tables.to_source();
+
+    assert!(source.starts_with("WeightsData {\n  index: &[\n    0x600, 0x610, "));
+    assert!(source.ends_with("  ],\n  continuous_block_end: 0x0FFF,\n}\n"));
+    assert!(source.lines().all(|line| line.len() <= 124));
+
+    let bytes = tables.to_bytes();
+    let buffer: Vec<u64> = bytes
+        .chunks(8)
+        .map(|chunk| u64::from_ne_bytes(chunk.try_into().unwrap()))
+        .collect();
+    let bytes = unsafe { core::slice::from_raw_parts(buffer.as_ptr() as *const u8, bytes.len()) };
+
+    let loaded = Collator::from_bytes(bytes, CollatorOptions::default()).unwrap();
+    let collator = collator();
+
+    for input in ["cha", "\u{E1}\u{316}", "\u{2126}", "b"] {
+        assert_eq!(loaded.get_weights(input), collator.get_weights(input));
+    }
+}
+
+/// ошибки в исходных данных
+#[test]
+fn test_build_errors()
+{
+    assert_eq!(
+        build(
+            "0061 ; [.2000.0020.0002]\n0062 ; .2001.0020.0002\n",
+            UNICODE_DATA
+        )
+        .err(),
+        Some(BuildError::InvalidAllKeys(2))
+    );
+
+    assert_eq!(
+        build("0061 ; [.2000.0200.0002]\n", UNICODE_DATA).err(),
+        Some(BuildError::WeightsOverflow(1))
+    );
+
+    assert_eq!(
+        build(ALLKEYS, "0061;LATIN SMALL LETTER A;Ll;x;L;;;;;N;;;;0041;\n").err(),
+        Some(BuildError::InvalidUnicodeData(1))
+    );
+
+    assert_eq!(
+        build(
+            "E0080 ; [.2000.0020.0002]\n100000 ; [.2001.0020.0002]\n",
+            UNICODE_DATA
+        )
+        .err(),
+        Some(BuildError::CodepointOutOfRange(0x100000))
+    );
+
+    // порядковый номер CCC записывается в 6 бит
+    let unicode_data: String = (1 ..= 64)
+        .map(|ccc| {
+            format!(
+                "{:04X};COMBINING MARK;Mn;{};NSM;;;;;N;;;;;\n",
+                0x0300 + ccc,
+                ccc
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        build(ALLKEYS, &unicode_data).err(),
+        Some(BuildError::TooManyCccClasses(65))
+    );
+}
+
+/// разбор исходного кода таблиц: data/cldr_und.txt
+#[test]
+fn test_tables_from_source()
+{
+    let source = include_str!("../../data/cldr_und.txt");
+
+    let tables = Tables::from_source(source).unwrap();
+    let expected = cldr_und();
+
+    assert_eq!(tables.index, expected.index);
+    assert_eq!(tables.scalars64, expected.scalars64);
+    assert_eq!(tables.scalars32, expected.scalars32);
+    assert_eq!(tables.expansions, expected.expansions);
+    assert_eq!(tables.tries, expected.tries);
+    assert_eq!(tables.continuous_block_end, expected.continuous_block_end);
+
+    assert_eq!(tables.to_source(), source);
+
+    assert_eq!(
+        Tables::from_source(&source.replacen("0x", "0y", 1)).err(),
+        Some(BuildError::InvalidSource(3))
+    );
+    assert_eq!(
+        Tables::from_source(&source.replacen("  tries: &[", "  trie: &[", 1)).err(),
+        Some(BuildError::InvalidTable("tries"))
+    );
+}
+
+/// сравнение собранных таблиц с эталонными
+#[test]
+fn test_compare()
+{
+    let tables = build(ALLKEYS, UNICODE_DATA).unwrap();
+    let expected = build(ALLKEYS, UNICODE_DATA).unwrap();
+
+    assert!(compare(&tables, &expected).is_identical());
+
+    let allkeys = ALLKEYS.replace("0068 ; [.2020", "0068 ; [.2021");
+    let modified = build(&allkeys, UNICODE_DATA).unwrap();
+
+    let comparison = compare(&modified, &expected);
+
+    assert!(!comparison.is_identical());
+    assert_eq!(comparison.codepoints, [0x68]);
+    assert_eq!(
+        comparison
+            .tables
+            .iter()
+            .map(|&(table, _)| table)
+            .collect::<Vec<_>>(),
+        ["scalars32"]
+    );
+}
+
+/// корневая сортировка CLDR из каталога common/uca
+#[test]
+fn test_build_cldr_root()
+{
+    let common = env::temp_dir().join(format!("cldr_common_{}", process::id()));
+    let uca_dir = common.join("uca");
+    let dtd = uca_dir.join(LDML_DTD);
+    let _ = fs::remove_dir_all(&common);
+
+    fs::create_dir_all(&uca_dir).unwrap();
+    fs::create_dir_all(common.join("dtd")).unwrap();
+
+    // есть только дробные веса
+    fs::write(uca_dir.join(FRACTIONAL_UCA), "[UCA version = 15.1.0]\n").unwrap();
+
+    assert_eq!(
+        build_cldr_root(&uca_dir, UNICODE_DATA).err(),
+        Some(BuildError::UnsupportedSource(uca_dir.join(FRACTIONAL_UCA)))
+    );
+
+    fs::write(uca_dir.join(ALLKEYS_CLDR), ALLKEYS).unwrap();
+
+    assert_eq!(
+        build_cldr_root(&uca_dir, UNICODE_DATA).err(),
+        Some(BuildError::ReadFailed(dtd.clone()))
+    );
+
+    fs::write(&dtd, "<!ATTLIST version cldrVersion CDATA #FIXED \"x\" >\n").unwrap();
+
+    assert_eq!(
+        build_cldr_root(&uca_dir, UNICODE_DATA).err(),
+        Some(BuildError::InvalidVersion(dtd.clone()))
+    );
+
+    for (cldr_version, expected) in [("44", [44, 0]), ("44.1", [44, 1])] {
+        let attlist = format!(
+            "<!ATTLIST version cldrVersion CDATA #FIXED \"{}\" >\n",
+            cldr_version
+        );
+
+        fs::write(&dtd, attlist).unwrap();
+
+        let tables = build_cldr_root(&uca_dir, UNICODE_DATA).unwrap();
+
+        assert_eq!(tables.version.cldr, expected);
+        assert_eq!(tables.version.unicode, [15, 1, 0]);
+        assert!(compare(&tables, &build(ALLKEYS, UNICODE_DATA).unwrap()).is_identical());
+    }
+
+    fs::remove_dir_all(&common).unwrap();
+
+    assert_eq!(
+        build_cldr_root(&uca_dir, UNICODE_DATA).err(),
+        Some(BuildError::ReadFailed(uca_dir.join(ALLKEYS_CLDR)))
+    );
+}
+
+/// таблицы, собранные из common/uca CLDR и UnicodeData.txt, совпадают с data/cldr_und.txt
+///
+/// исходных файлов в репозитории нет - каталог common CLDR и UnicodeData.txt той же версии Unicode
+/// задаются переменными окружения:
+///
+/// CLDR_COMMON=cldr/common UNICODE_DATA=UnicodeData.txt cargo test --test builder -- --ignored
+#[test]
+#[ignore = "нужны CLDR_COMMON и UNICODE_DATA"]
+fn test_cldr_und_conformance()
+{
+    let common = env::var("CLDR_COMMON").expect("не задан CLDR_COMMON - каталог common CLDR");
+    let unicode_data = env::var("UNICODE_DATA").expect("не задан UNICODE_DATA - UnicodeData.txt");
+
+    let unicode_data = fs::read_to_string(unicode_data).unwrap();
+    let tables = build_cldr_root(&Path::new(&common).join("uca"), &unicode_data).unwrap();
+    let expected = Tables::from_source(include_str!("../../data/cldr_und.txt")).unwrap();
+
+    let comparison = compare(&tables, &expected);
+
+    assert!(
+        comparison.codepoints.is_empty(),
+        "{:X?}",
+        comparison.codepoints
+    );
+    assert!(comparison.is_identical(), "{:?}", comparison.tables);
+    assert_ne!(tables.version.cldr, [0, 0]);
+}
@@ -0,0 +1,95 @@
+use unicode_collator::{
+    key::{compare_keys, KeyFormat},
+    options::{AlternateHandling, CollatorOptions, Strength},
+    Collator,
+};
+use unicode_data::{COLLATION_TEST_CLDR_NON_IGNORABLE, COLLATION_TEST_CLDR_SHIFTED};
+
+/// побайтовое сравнение ключей (в т.ч. сжатых) должно совпадать с compare_keys
+#[test]
+fn test_sort_key_bytes()
+{
+    for (tests, alternate) in [
+        (
+            &COLLATION_TEST_CLDR_NON_IGNORABLE,
+            AlternateHandling::NonIgnorable,
+        ),
+        (&COLLATION_TEST_CLDR_SHIFTED, AlternateHandling::Shifted),
+    ] {
+        for strength in [
+            Strength::Primary,
+            Strength::Secondary,
+            Strength::Tetriary,
+            Strength::Quaternary,
+        ] {
+            let collator = Collator::new(CollatorOptions {
+                strength,
+                alternate,
+                ..Default::default()
+            });
+
+            let mut prev: Option<(Vec<u16>, Vec<u8>, Vec<u8>)> = None;
+
+            for test in tests.iter() {
+                let input = test.as_string();
+
+                let key = collator.get_key(&input);
+                let bytes = key.to_bytes();
+                let compressed = key.to_compressed_bytes();
+
+                assert_eq!(
+                    bytes,
+                    collator.get_sort_key_bytes(&input, KeyFormat::Uncompressed)
+                );
+                assert_eq!(
+                    compressed,
+                    collator.get_sort_key_bytes(&input, KeyFormat::Compressed)
+                );
+                assert!(compressed.len() <= bytes.len());
+
+                if let Some((prev_key, prev_bytes, prev_compressed)) = prev {
+                    let expected = compare_keys(&prev_key, &key.weights);
+
+                    assert_eq!(prev_bytes.cmp(&bytes), expected, "{}", test.description);
+                    assert_eq!(
+                        prev_compressed.cmp(&compressed),
+                        expected,
+                        "{}",
+                        test.description
+                    );
+                }
+
+                prev = Some((key.weights, bytes, compressed));
+            }
+        }
+    }
+}
+
+/// длинные серии общих весов вторичного и третичного уровней
+#[test]
+fn test_compressed_runs()
+{
+    let collator = Collator::new(CollatorOptions::default());
+
+    let mut strings = vec![];
+
+    for count in [1, 2, 61, 62, 63, 124, 125, 200] {
+        for tail in ["", "\u{301}", "A", "\u{300}a"] {
+            strings.push(format!("{}{}", "a".repeat(count), tail));
+        }
+    }
+
+    for a in strings.iter() {
+        for b in strings.iter() {
+            let a_key = collator.get_key(a);
+            let b_key = collator.get_key(b);
+
+            assert_eq!(
+                a_key
+                    .to_compressed_bytes()
+                    .cmp(&b_key.to_compressed_bytes()),
+                compare_keys(&a_key.weights, &b_key.weights)
+            );
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use core::cmp::Ordering;
+
+use unicode_collator::{
+    key::compare_keys,
+    options::{CaseFirst, CollatorOptions, Strength},
+    Collator,
+};
+
+/// порядок регистров на третичном уровне
+#[test]
+fn test_case_first()
+{
+    for (case_first, expected) in [
+        (CaseFirst::Off, ["a", "ａ", "A", "Ａ", "ª", "ᴬ"]),
+        (CaseFirst::Upper, ["A", "Ａ", "ᴬ", "a", "ａ", "ª"]),
+        (CaseFirst::Lower, ["a", "ａ", "ª", "A", "Ａ", "ᴬ"]),
+    ] {
+        let collator = Collator::new(CollatorOptions {
+            case_first,
+            ..Default::default()
+        });
+
+        let mut strings = ["ᴬ", "ª", "Ａ", "A", "ａ", "a"];
+
+        strings.sort_by_key(|s| collator.get_key(s).weights);
+        assert_eq!(strings, expected, "{:?}", case_first);
+
+        strings.sort_by(|a, b| collator.compare(a, b));
+        assert_eq!(strings, expected, "{:?}", case_first);
+    }
+}
+
+/// caseFirst не влияет на первичный и вторичный уровни
+#[test]
+fn test_case_first_levels()
+{
+    let collator = Collator::new(CollatorOptions {
+        case_first: CaseFirst::Upper,
+        ..Default::default()
+    });
+
+    let mut strings = ["ab", "Ab", "AB", "áb", "b"];
+
+    strings.sort_by(|a, b| collator.compare(a, b));
+    assert_eq!(strings, ["AB", "Ab", "ab", "áb", "b"]);
+}
+
+/// caseLevel: первичный уровень с учётом регистра
+#[test]
+fn test_case_level()
+{
+    let collator = Collator::new(CollatorOptions {
+        strength: Strength::Primary,
+        case_level: true,
+        ..Default::default()
+    });
+
+    for (a, b, expected) in [
+        ("résumé", "resume", Ordering::Equal),
+        ("Resume", "resume", Ordering::Greater),
+        ("Résumé", "resume", Ordering::Greater),
+        ("Resume", "resumes", Ordering::Less),
+    ] {
+        assert_eq!(collator.compare(a, b), expected, "{} {}", a, b);
+        assert_eq!(
+            compare_keys(&collator.get_key(a).weights, &collator.get_key(b).weights),
+            expected,
+            "{} {}",
+            a,
+            b
+        );
+    }
+}
+
+/// уровень регистра важнее вторичного уровня, порядок регистров задаётся caseFirst
+#[test]
+fn test_case_level_order()
+{
+    for (case_first, expected) in [
+        (CaseFirst::Off, ["ab", "áb", "Ab", "AB"]),
+        (CaseFirst::Upper, ["AB", "Ab", "ab", "áb"]),
+    ] {
+        let collator = Collator::new(CollatorOptions {
+            strength: Strength::Secondary,
+            case_first,
+            case_level: true,
+            ..Default::default()
+        });
+
+        let mut strings = ["áb", "AB", "Ab", "ab"];
+
+        strings.sort_by(|a, b| collator.compare(a, b));
+        assert_eq!(strings, expected, "{:?}", case_first);
+
+        let key = collator.get_key("Ab");
+
+        assert!(key.case_level);
+        assert_eq!(key.levels_count(), 3);
+        assert_eq!(key.level(2), collator.get_key("ab").level(2));
+    }
+}
@@ -0,0 +1,189 @@
+use unicode_collator::{
+    key::compare_keys,
+    options::{
+        AlternateHandling, CaseFirst, CollatorOptions, MaxVariable, QuaternaryMode, ReorderCode,
+        Strength,
+    },
+    Collator,
+};
+use unicode_data::{COLLATION_TEST_CLDR_NON_IGNORABLE, COLLATION_TEST_CLDR_SHIFTED};
+
+/// прямое сравнение строк должно совпадать со сравнением ключей
+#[test]
+fn test_compare()
+{
+    for (tests, alternate) in [
+        (
+            &COLLATION_TEST_CLDR_NON_IGNORABLE,
+            AlternateHandling::NonIgnorable,
+        ),
+        (&COLLATION_TEST_CLDR_SHIFTED, AlternateHandling::Shifted),
+    ] {
+        for strength in [
+            Strength::Primary,
+            Strength::Secondary,
+            Strength::Tetriary,
+            Strength::Quaternary,
+        ] {
+            let collator = Collator::new(CollatorOptions {
+                strength,
+                alternate,
+                ..Default::default()
+            });
+
+            let strings: Vec<String> = tests.iter().map(|test| test.as_string()).collect();
+            let keys: Vec<Vec<u16>> = strings
+                .iter()
+                .map(|s| collator.get_key(s).weights)
+                .collect();
+
+            for i in 1 .. strings.len() {
+                for j in [i - 1, i / 2] {
+                    assert_eq!(
+                        collator.compare(&strings[j], &strings[i]),
+                        compare_keys(&keys[j], &keys[i]),
+                        "{:?} / {:?}",
+                        strings[j],
+                        strings[i]
+                    );
+                    assert_eq!(
+                        collator.compare(&strings[i], &strings[j]),
+                        compare_keys(&keys[i], &keys[j]),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// строки, на которых различаются результаты опций: регистр, диакритика, переменные веса, числа,
+/// письменности, сокращения, нестартеры и игнорируемые кодпоинты
+const OPTIONS_STRINGS: [&str; 32] = [
+    "",
+    "a",
+    "A",
+    "á",
+    "a\u{301}",
+    "Á",
+    "ab",
+    "aB",
+    "Ab",
+    "a b",
+    "a-b",
+    "a_b",
+    "a$b",
+    "a+b",
+    "a\u{AD}b",
+    "côte",
+    "coté",
+    "côté",
+    "cote",
+    "a2",
+    "a10",
+    "a02",
+    "a9z",
+    "٣",
+    "αβ",
+    "Ωb",
+    "яблоко",
+    "Яблоко",
+    "ch",
+    "l·l",
+    "\u{FFFE}a",
+    "中",
+];
+
+/// прямое сравнение строк совпадает со сравнением ключей при любом сочетании опций
+#[test]
+fn test_compare_options()
+{
+    let collator = Collator::new(CollatorOptions::default());
+
+    for options in all_options() {
+        let collator = collator.with_options(options.clone()).unwrap();
+        let keys: Vec<Vec<u16>> = OPTIONS_STRINGS
+            .iter()
+            .map(|s| collator.get_key(s).weights)
+            .collect();
+
+        for (i, a) in OPTIONS_STRINGS.iter().enumerate() {
+            for (j, b) in OPTIONS_STRINGS.iter().enumerate() {
+                assert_eq!(
+                    collator.compare(a, b),
+                    compare_keys(&keys[i], &keys[j]),
+                    "{:?} / {:?}, {:?}",
+                    a,
+                    b,
+                    options
+                );
+            }
+        }
+    }
+}
+
+/// все сочетания опций, влияющих на ключ
+fn all_options() -> Vec<CollatorOptions>
+{
+    let mut result = vec![CollatorOptions::default()];
+
+    macro_rules! vary {
+        ($field: ident, $values: expr) => {
+            result = result
+                .into_iter()
+                .flat_map(|options| {
+                    $values.into_iter().map(move |$field| CollatorOptions {
+                        $field,
+                        ..options.clone()
+                    })
+                })
+                .collect();
+        };
+    }
+
+    vary!(
+        strength,
+        [
+            Strength::Primary,
+            Strength::Secondary,
+            Strength::Tetriary,
+            Strength::Quaternary,
+            Strength::Identical,
+        ]
+    );
+    vary!(
+        alternate,
+        [
+            AlternateHandling::NonIgnorable,
+            AlternateHandling::Shifted,
+            AlternateHandling::Blanked,
+            AlternateHandling::ShiftTrimmed,
+        ]
+    );
+    vary!(
+        max_variable,
+        [
+            MaxVariable::Space,
+            MaxVariable::Punct,
+            MaxVariable::Symbol,
+            MaxVariable::Currency,
+        ]
+    );
+    vary!(quaternary_mode, [QuaternaryMode::Cldr, QuaternaryMode::Uca]);
+    vary!(
+        case_first,
+        [CaseFirst::Off, CaseFirst::Upper, CaseFirst::Lower]
+    );
+    vary!(case_level, [false, true]);
+    vary!(backwards_secondary, [false, true]);
+    vary!(numeric, [false, true]);
+    vary!(
+        reorder,
+        [
+            vec![],
+            vec![ReorderCode::Script(*b"Grek"), ReorderCode::Digit],
+            vec![ReorderCode::Others, ReorderCode::Space, ReorderCode::Punct],
+        ]
+    );
+
+    result
+}
@@ -0,0 +1,157 @@
+use unicode_collator::{
+    cldr_und,
+    container::{self, ContainerError, DataVersion},
+    options::CollatorOptions,
+    Collator,
+};
+
+const VERSION: DataVersion = DataVersion {
+    unicode: [15, 1, 0],
+    cldr: [44, 0],
+};
+
+/// копия байт в буфере, выровненном по 8 байтам
+fn aligned(bytes: &[u8]) -> Vec<u64>
+{
+    bytes
+        .chunks(8)
+        .map(|chunk| {
+            let mut value = [0; 8];
+            value[.. chunk.len()].copy_from_slice(chunk);
+            u64::from_ne_bytes(value)
+        })
+        .collect()
+}
+
+/// байты выровненного буфера
+fn as_bytes(buffer: &[u64], len: usize) -> &[u8]
+{
+    unsafe { core::slice::from_raw_parts(buffer.as_ptr() as *const u8, len) }
+}
+
+/// таблицы, прочитанные из контейнера, совпадают с исходными
+#[test]
+fn test_container_roundtrip()
+{
+    let bytes = container::to_bytes(&cldr_und(), VERSION);
+
+    let mut written = vec![];
+    container::write(&mut written, &cldr_und(), VERSION).unwrap();
+    assert_eq!(written, bytes);
+
+    let buffer = aligned(&bytes);
+    let bytes = as_bytes(&buffer, bytes.len());
+
+    let (data, version) = container::read(bytes).unwrap();
+    let source = cldr_und();
+
+    assert_eq!(version, VERSION);
+    assert_eq!(container::read_version(bytes), Ok(VERSION));
+    assert_eq!(data.index, source.index);
+    assert_eq!(data.scalars32, source.scalars32);
+    assert_eq!(data.scalars64, source.scalars64);
+    assert_eq!(data.expansions, source.expansions);
+    assert_eq!(data.tries, source.tries);
+    assert_eq!(data.continuous_block_end, source.continuous_block_end);
+
+    // таблицы не копируются
+    assert!(bytes
+        .as_ptr_range()
+        .contains(&(data.tries.as_ptr() as *const u8)));
+
+    let loaded = Collator::from_bytes(bytes, CollatorOptions::default()).unwrap();
+    let baked = Collator::new(CollatorOptions::default());
+
+    for input in [
+        "abc",
+        "ABC",
+        "a-b",
+        "Ёлка",
+        "ελλάδα",
+        "한국어",
+        "\u{FDFA}",
+        "𝟘",
+    ] {
+        assert_eq!(
+            loaded.get_key(input).weights,
+            baked.get_key(input).weights,
+            "{:?}",
+            input
+        );
+    }
+}
+
+/// повреждённые и неподходящие контейнеры
+#[test]
+fn test_container_errors()
+{
+    let source = container::to_bytes(&cldr_und(), VERSION);
+    let len = source.len();
+
+    let read = |bytes: &[u8]| {
+        let buffer = aligned(bytes);
+        container::read(as_bytes(&buffer, bytes.len())).map(|_| ())
+    };
+
+    assert_eq!(read(&source), Ok(()));
+
+    let mut bytes = source.clone();
+    bytes[0] = b'X';
+    assert_eq!(read(&bytes), Err(ContainerError::InvalidMagic));
+
+    let mut bytes = source.clone();
+    bytes[4] = 2;
+    assert_eq!(read(&bytes), Err(ContainerError::UnknownVersion(2)));
+
+    let mut bytes = source.clone();
+    bytes[6] = 1;
+    assert_eq!(read(&bytes), Err(ContainerError::InvalidHeader));
+
+    assert_eq!(
+        read(&source[.. len - 16]),
+        Err(ContainerError::InvalidLength {
+            expected: len,
+            actual: len - 16
+        })
+    );
+
+    assert_eq!(
+        read(&source[.. 10]),
+        Err(ContainerError::InvalidLength {
+            expected: container::HEADER_SIZE,
+            actual: 10
+        })
+    );
+
+    let u32_at =
+        |bytes: &[u8], pos: usize| u32::from_le_bytes(bytes[pos .. pos + 4].try_into().unwrap());
+
+    // данные таблиц и continuous_block_end
+    for pos in [container::HEADER_SIZE, 16] {
+        let mut bytes = source.clone();
+        bytes[pos] ^= 1;
+        assert!(
+            matches!(read(&bytes), Err(ContainerError::ChecksumMismatch { .. })),
+            "{}",
+            pos
+        );
+    }
+
+    // длины таблиц scalars32 и expansions изменены без изменения длины контейнера
+    let mut bytes = source.clone();
+    let scalars32 = u32_at(&bytes, 24) + 4;
+    let expansions = u32_at(&bytes, 28) - 4;
+    bytes[24 .. 28].copy_from_slice(&scalars32.to_le_bytes());
+    bytes[28 .. 32].copy_from_slice(&expansions.to_le_bytes());
+    assert!(matches!(
+        read(&bytes),
+        Err(ContainerError::ChecksumMismatch { .. })
+    ));
+
+    // смещение на 4 байта от выровненного начала
+    let buffer = aligned(&[&[0; 4], source.as_slice()].concat());
+    assert_eq!(
+        container::read(&as_bytes(&buffer, len + 4)[4 ..]).map(|_| ()),
+        Err(ContainerError::Misaligned)
+    );
+}
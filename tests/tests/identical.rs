@@ -0,0 +1,73 @@
+use core::cmp::Ordering;
+
+use unicode_collator::{
+    key::compare_keys,
+    options::{AlternateHandling, CollatorOptions, Strength},
+    Collator,
+};
+use unicode_data::{COLLATION_TEST_CLDR_NON_IGNORABLE, COLLATION_TEST_CLDR_SHIFTED};
+
+/// строки в тестах CLDR отсортированы с учётом идентичного уровня
+#[test]
+fn test_identical_order()
+{
+    for (tests, alternate) in [
+        (
+            &COLLATION_TEST_CLDR_NON_IGNORABLE,
+            AlternateHandling::NonIgnorable,
+        ),
+        (&COLLATION_TEST_CLDR_SHIFTED, AlternateHandling::Shifted),
+    ] {
+        let collator = Collator::new(CollatorOptions {
+            strength: Strength::Identical,
+            alternate,
+            ..Default::default()
+        });
+
+        let mut prev: Option<(String, Vec<u16>)> = None;
+
+        for test in tests.iter() {
+            let input = test.as_string();
+            let key = collator.get_key(&input).weights;
+
+            if let Some((prev_input, prev_key)) = prev {
+                let compare = compare_keys(&prev_key, &key);
+
+                assert_ne!(compare, Ordering::Greater, "{}", test.description);
+                assert_eq!(collator.compare(&prev_input, &input), compare);
+            }
+
+            prev = Some((input, key));
+        }
+    }
+}
+
+/// идентичный уровень различает строки, совпадающие на остальных уровнях,
+/// но не канонически эквивалентные
+#[test]
+fn test_identical_level()
+{
+    let quaternary = Collator::new(CollatorOptions {
+        strength: Strength::Quaternary,
+        alternate: AlternateHandling::Shifted,
+        ..Default::default()
+    });
+    let identical = Collator::new(CollatorOptions {
+        strength: Strength::Identical,
+        alternate: AlternateHandling::Shifted,
+        ..Default::default()
+    });
+
+    assert_eq!(quaternary.compare("a\u{1}b", "ab"), Ordering::Equal);
+    assert_eq!(identical.compare("a\u{1}b", "ab"), Ordering::Less);
+
+    // канонически эквивалентные строки равны и на идентичном уровне
+    for (a, b) in [
+        ("\u{C5}", "\u{212B}"),
+        ("\u{E9}", "e\u{301}"),
+        ("\u{1E69}", "s\u{307}\u{323}"),
+    ] {
+        assert_eq!(identical.compare(a, b), Ordering::Equal);
+        assert_eq!(identical.get_key(a).weights, identical.get_key(b).weights);
+    }
+}
@@ -1,7 +1,7 @@
 use core::cmp::Ordering;
 
 use unicode_collator::{
-    key::compare_keys, options::{AlternateHandling, CollatorOptions, Strength}, weights::Weights, Collator
+    key::compare_keys, options::{AlternateHandling, CollatorOptions, QuaternaryMode, Strength}, weights::Weights, Collator
 };
 use unicode_data::{CollationTest, COLLATION_TEST_CLDR_NON_IGNORABLE, COLLATION_TEST_CLDR_SHIFTED};
 
@@ -19,6 +19,7 @@ fn test_non_ignorable()
         let collator = Collator::new(CollatorOptions {
             strength,
             alternate: AlternateHandling::NonIgnorable,
+            ..Default::default()
         });
 
         let mut prev = vec![];
@@ -57,6 +58,8 @@ fn test_shifted()
         let collator = Collator::new(CollatorOptions {
             strength,
             alternate: AlternateHandling::Shifted,
+            quaternary_mode: QuaternaryMode::Cldr,
+            ..Default::default()
         });
 
         let mut prev = vec![];
@@ -69,19 +72,8 @@ fn test_shifted()
 
             let compare = compare_keys(&prev, &key);
 
-            /*
-                TODO:
-
-                похоже, я чего-то не понимаю в тестах CLDR / #TR 10 - странно считается 4й уровень весов
-                пример: 3358 0021 - если следовать документации, то мы получим дополнительный L4 FFFF.
-                если же смотреть на результат в файле текста, то можно предположить проверку L3 != 0 в последнем
-                пункте.
-
-                второй момент связан с весами U+FFFE. согласно документации, добавляется вес FFFF, согласно
-                тестам - 0001.
-
-                на данный момент привёл код в соответствие с результатами тестов CLDR.
-            */
+            // четвертичный уровень в тестах CLDR соответствует QuaternaryMode::Cldr
+            // (см. ISSUES_SHIFTED.md)
             if strength == Strength::Quaternary && test_key != key {
                 let codes: String = test.codes.iter().map(|c| format!("{:04X} ", c)).collect();
                 let weights: String = weights.iter().map(|w| Weights::from(*w).format()).collect();
@@ -101,9 +93,7 @@ fn test_shifted()
         }
     }
 
-    if errors_count != 0 {
-        println!("ошибок: {}", errors_count);
-    }
+    assert_eq!(errors_count, 0);
 }
 
 fn test_to_key(test: &CollationTest, levels: u8) -> Vec<u16>
@@ -0,0 +1,57 @@
+use unicode_collator::{
+    key::{compare_keys, merge_keys},
+    options::{CollatorOptions, Strength},
+    Collator,
+};
+
+/// сортировка записей (фамилия, имя) по объединённым ключам
+#[test]
+fn test_merge_keys()
+{
+    let collator = Collator::new(CollatorOptions::default());
+
+    let mut records = [
+        ("Smithson", "Al"),
+        ("Smith", "Zed"),
+        ("Smith", "John"),
+        ("smith", "Bob"),
+        ("Smith", "Anna"),
+        ("Smith", "anna"),
+    ];
+
+    records.sort_by(|a, b| {
+        let a = merge_keys(&[collator.get_key(a.0), collator.get_key(a.1)]);
+        let b = merge_keys(&[collator.get_key(b.0), collator.get_key(b.1)]);
+
+        compare_keys(&a.weights, &b.weights)
+    });
+
+    assert_eq!(
+        records,
+        [
+            ("Smith", "anna"),
+            ("Smith", "Anna"),
+            ("smith", "Bob"),
+            ("Smith", "John"),
+            ("Smith", "Zed"),
+            ("Smithson", "Al"),
+        ]
+    );
+}
+
+/// на первичном уровне объединённый ключ совпадает с ключом строки, поля которой разделены U+FFFE
+#[test]
+fn test_merge_separator()
+{
+    let collator = Collator::new(CollatorOptions {
+        strength: Strength::Primary,
+        ..Default::default()
+    });
+
+    let merged = merge_keys(&[collator.get_key("Smith"), collator.get_key("John")]);
+
+    assert_eq!(
+        merged.weights,
+        collator.get_key("Smith\u{FFFE}John").weights
+    );
+}
@@ -0,0 +1,54 @@
+use core::cmp::Ordering;
+
+use unicode_collator::{is_fcd, options::CollatorOptions, Collator};
+
+/// проверка формы FCD
+#[test]
+fn test_is_fcd()
+{
+    for (input, expected) in [
+        ("", true),
+        ("abc", true),
+        ("한국어", true),
+        ("\u{316}", true),
+        ("a\u{316}\u{301}", true),
+        ("a\u{301}\u{316}", false),
+        ("\u{1E0D}\u{307}", true),
+        ("\u{1E0B}\u{323}", false),
+        ("\u{F71}\u{F73}", true),
+        ("\u{F73}\u{F71}", false),
+    ] {
+        assert_eq!(is_fcd(input), expected, "{:?}", input);
+    }
+}
+
+/// без нормализации строки в форме FCD сравниваются так же, как с нормализацией, прочие - в
+/// порядке записи нестартеров
+#[test]
+fn test_normalization()
+{
+    let normalized = Collator::new(CollatorOptions::default());
+    let trusted = Collator::new(CollatorOptions {
+        normalization: false,
+        ..Default::default()
+    });
+
+    for input in [
+        "a\u{316}\u{301}",
+        "\u{1E0D}\u{307}",
+        "\u{F71}\u{F73}",
+        "Ǻ\u{301}",
+    ] {
+        assert!(is_fcd(input));
+        assert_eq!(
+            normalized.get_key(input).weights,
+            trusted.get_key(input).weights
+        );
+    }
+
+    let (a, b) = ("a\u{316}\u{301}", "a\u{301}\u{316}");
+
+    assert_eq!(normalized.compare(a, b), Ordering::Equal);
+    assert_ne!(trusted.compare(a, b), Ordering::Equal);
+    assert_ne!(trusted.get_key(a).weights, trusted.get_key(b).weights);
+}
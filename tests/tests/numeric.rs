@@ -0,0 +1,157 @@
+use core::cmp::Ordering;
+
+use unicode_collation_builder::build;
+use unicode_collator::{
+    key::compare_keys,
+    options::{CollatorOptions, OptionsError, Strength},
+    Collator, DataError,
+};
+
+const UNICODE_DATA: &str = "\
+0030;DIGIT ZERO;Nd;0;EN;;0;0;0;N;;;;;
+0031;DIGIT ONE;Nd;0;EN;;1;1;1;N;;;;;
+0061;LATIN SMALL LETTER A;Ll;0;L;;;;;N;;;;0041;
+";
+
+/// последовательности цифр сортируются по числовому значению
+#[test]
+fn test_numeric_order()
+{
+    let collator = Collator::new(CollatorOptions {
+        numeric: true,
+        ..Default::default()
+    });
+
+    let expected = [
+        "file",
+        "file0",
+        "file1",
+        "file1.5",
+        "file1.10",
+        "file2",
+        "file9",
+        "file10",
+        "file9999",
+        "file10000",
+        "filea",
+    ];
+
+    let mut strings = expected;
+    strings.reverse();
+
+    strings.sort_by(|a, b| collator.compare(a, b));
+    assert_eq!(strings, expected);
+
+    strings.reverse();
+    strings
+        .sort_by(|a, b| compare_keys(&collator.get_key(a).weights, &collator.get_key(b).weights));
+    assert_eq!(strings, expected);
+}
+
+/// цифры любых систем счисления (Nd), ведущие нули
+#[test]
+fn test_numeric_digits()
+{
+    let collator = Collator::new(CollatorOptions {
+        numeric: true,
+        ..Default::default()
+    });
+
+    for (a, b, expected) in [
+        ("2", "10", Ordering::Less),
+        ("١١", "2", Ordering::Greater),  // арабско-индийские
+        ("१०", "9", Ordering::Greater),  // деванагари
+        ("１２", "12", Ordering::Equal), // полноширинные
+        ("007", "7", Ordering::Equal),
+        ("000", "0", Ordering::Equal),
+        ("0", "1", Ordering::Less),
+        ("a10b", "a9c", Ordering::Greater),
+    ] {
+        assert_eq!(collator.compare(a, b), expected, "{} {}", a, b);
+        assert_eq!(
+            compare_keys(&collator.get_key(a).weights, &collator.get_key(b).weights),
+            expected,
+            "{} {}",
+            a,
+            b
+        );
+    }
+
+    // ведущие нули и система счисления различаются на идентичном уровне
+    let identical = Collator::new(CollatorOptions {
+        strength: Strength::Identical,
+        numeric: true,
+        ..Default::default()
+    });
+
+    assert_ne!(identical.compare("007", "7"), Ordering::Equal);
+    assert_ne!(identical.compare("١٢", "12"), Ordering::Equal);
+}
+
+/// длинные последовательности цифр: до 254 значащих цифр сравниваются как одно число,
+/// более длинные - как несколько последовательных чисел
+#[test]
+fn test_numeric_long()
+{
+    let collator = Collator::new(CollatorOptions {
+        numeric: true,
+        ..Default::default()
+    });
+
+    let nines = "9".repeat(200);
+    let power = format!("1{}", "0".repeat(200));
+
+    assert_eq!(collator.compare(&nines, &power), Ordering::Less);
+    assert_eq!(
+        collator.compare(&power, &format!("000{}", power)),
+        Ordering::Equal
+    );
+
+    let long = "9".repeat(300);
+
+    assert_eq!(
+        collator.compare(&long, &format!("{}1", long)),
+        Ordering::Less
+    );
+    assert_eq!(
+        collator.compare(
+            &format!("{}1", "9".repeat(254)),
+            &format!("{}2", "9".repeat(254))
+        ),
+        Ordering::Less
+    );
+}
+
+/// веса числа начинаются с первичного веса цифры 0 из таблиц коллатора
+#[test]
+fn test_numeric_table_primary()
+{
+    let options = CollatorOptions {
+        numeric: true,
+        ..Default::default()
+    };
+
+    let allkeys = "\
+@version 15.1.0
+
+0030 ; [.2100.0020.0002] # DIGIT ZERO
+0031 ; [.2101.0020.0002] # DIGIT ONE
+0061 ; [.2000.0020.0002] # LATIN SMALL LETTER A
+";
+
+    let tables = build(allkeys, UNICODE_DATA).unwrap();
+    let collator = Collator::try_from_baked(tables.as_weights_data(), options.clone()).unwrap();
+
+    assert_eq!(collator.get_weights("10")[0] as u16, 0x2100);
+    assert_eq!(collator.compare("a2", "a10"), Ordering::Less);
+    assert_eq!(collator.compare("10", "a"), Ordering::Greater);
+
+    // у цифры 0 нет первичного веса - числовое сопоставление невозможно
+    let allkeys = allkeys.replace("[.2100.0020.0002]", "[.0000.0020.0002]");
+    let tables = build(&allkeys, UNICODE_DATA).unwrap();
+
+    assert_eq!(
+        Collator::try_from_baked(tables.as_weights_data(), options).err(),
+        Some(DataError::UnsupportedOptions(OptionsError::Numeric))
+    );
+}
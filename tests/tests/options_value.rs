@@ -0,0 +1,140 @@
+use unicode_collator::options::{
+    AlternateHandling, CaseFirst, CollatorOptions, CollatorOptionsValue, MaxVariable,
+    OptionsValueError, QuaternaryMode, ReorderCode, Strength,
+};
+use unicode_collator::Collator;
+
+/// все опции, включая порядок групп, восстанавливаются из сохранённого значения
+#[test]
+fn test_options_value()
+{
+    let options = CollatorOptions {
+        strength: Strength::Identical,
+        alternate: AlternateHandling::ShiftTrimmed,
+        max_variable: MaxVariable::Symbol,
+        quaternary_mode: QuaternaryMode::Uca,
+        case_first: CaseFirst::Lower,
+        case_level: true,
+        backwards_secondary: true,
+        numeric: true,
+        reorder: vec![
+            ReorderCode::Script(*b"Cyrl"),
+            ReorderCode::Digit,
+            ReorderCode::Others,
+            ReorderCode::Script(*b"Hira"),
+        ],
+        normalization: false,
+    };
+
+    let value = CollatorOptionsValue::from(&options);
+    let bytes = value.as_bytes().to_vec();
+
+    assert_eq!(bytes.len(), 3 + 4 * 4);
+
+    let restored = CollatorOptions::try_from(CollatorOptionsValue::from_bytes(&bytes)).unwrap();
+
+    assert_eq!(format!("{:?}", restored), format!("{:?}", options));
+}
+
+/// значения прежнего формата (u16)
+#[test]
+fn test_options_value_legacy()
+{
+    let options = CollatorOptions::try_from(CollatorOptionsValue::from(0x0003)).unwrap();
+
+    assert_eq!(options.strength, Strength::Tetriary);
+    assert_eq!(options.alternate, AlternateHandling::NonIgnorable);
+    assert!(options.normalization);
+    assert!(options.reorder.is_empty());
+
+    let options = CollatorOptions::try_from(CollatorOptionsValue::from(0x000C)).unwrap();
+
+    assert_eq!(options.strength, Strength::Quaternary);
+    assert_eq!(options.alternate, AlternateHandling::Shifted);
+    assert_eq!(options.max_variable, MaxVariable::Punct);
+
+    // в прежнем формате пунктуация - переменные символы, ключи совпадают с прежними
+    let options = CollatorOptions::try_from(CollatorOptionsValue::from(0x000B)).unwrap();
+
+    assert_eq!(options.max_variable, MaxVariable::Punct);
+
+    let collator = Collator::new(options);
+    let expected = Collator::new(CollatorOptions {
+        strength: Strength::Tetriary,
+        alternate: AlternateHandling::Shifted,
+        max_variable: MaxVariable::Punct,
+        ..Default::default()
+    });
+
+    assert_eq!(
+        collator.get_key("a-b").weights,
+        expected.get_key("a-b").weights
+    );
+    assert_eq!(
+        collator.get_key("a-b").weights,
+        collator.get_key("ab").weights
+    );
+
+    // обратно в u16 - значения без порядка групп
+    assert_eq!(
+        u16::try_from(&CollatorOptionsValue::from(0x000C)),
+        Ok(0x000C)
+    );
+
+    let mut options = CollatorOptions {
+        strength: Strength::Quaternary,
+        alternate: AlternateHandling::Shifted,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        u16::try_from(&CollatorOptionsValue::from(&options)),
+        Ok(0x000C)
+    );
+
+    options.reorder = vec![ReorderCode::Digit];
+
+    assert_eq!(
+        u16::try_from(&CollatorOptionsValue::from(&options)),
+        Err(OptionsValueError::ReorderInLegacyFormat)
+    );
+    assert_eq!(
+        u16::try_from(&CollatorOptionsValue::from_bytes(&[2, 0, 3])),
+        Err(OptionsValueError::UnknownVersion(2))
+    );
+}
+
+/// повреждённые значения не приводят к некорректным опциям
+#[test]
+fn test_options_value_errors()
+{
+    for (bytes, error) in [
+        (vec![], OptionsValueError::InvalidLength(0)),
+        (vec![1, 0], OptionsValueError::InvalidLength(2)),
+        (vec![0, 0, 3, 0], OptionsValueError::InvalidLength(4)),
+        (vec![1, 0, 3, b'C'], OptionsValueError::InvalidLength(4)),
+        (vec![9, 0, 3], OptionsValueError::UnknownVersion(9)),
+        (vec![1, 0, 0], OptionsValueError::InvalidFlags(0)),
+        (vec![1, 0, 6], OptionsValueError::InvalidFlags(6)),
+        (vec![0, 0, 7], OptionsValueError::InvalidFlags(7)),
+        (vec![1, 0, 0x33], OptionsValueError::InvalidFlags(0x33)),
+        (vec![1, 0x40, 3], OptionsValueError::InvalidFlags(0x4003)),
+        (
+            vec![1, 0, 3, b'A', b'b', b'c', b'd'],
+            OptionsValueError::InvalidReorderCode(*b"Abcd"),
+        ),
+        (
+            vec![1, 0, 3, 0, 0, 0, 6],
+            OptionsValueError::InvalidReorderCode([0, 0, 0, 6]),
+        ),
+    ] {
+        let value = CollatorOptionsValue::from_bytes(&bytes);
+
+        assert_eq!(
+            CollatorOptions::try_from(value).unwrap_err(),
+            error,
+            "{:?}",
+            bytes
+        );
+    }
+}
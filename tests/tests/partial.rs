@@ -0,0 +1,68 @@
+use unicode_collator::{
+    options::{AlternateHandling, CollatorOptions, Strength},
+    Collator,
+};
+use unicode_data::{COLLATION_TEST_CLDR_NON_IGNORABLE, COLLATION_TEST_CLDR_SHIFTED};
+
+/// ключ, собранный из частей, должен совпадать с Key::to_bytes
+#[test]
+fn test_sort_key_parts()
+{
+    for (tests, alternate) in [
+        (
+            &COLLATION_TEST_CLDR_NON_IGNORABLE,
+            AlternateHandling::NonIgnorable,
+        ),
+        (&COLLATION_TEST_CLDR_SHIFTED, AlternateHandling::Shifted),
+    ] {
+        for strength in [
+            Strength::Primary,
+            Strength::Secondary,
+            Strength::Tetriary,
+            Strength::Quaternary,
+        ] {
+            let collator = Collator::new(CollatorOptions {
+                strength,
+                alternate,
+                ..Default::default()
+            });
+
+            for test in tests.iter() {
+                let input = test.as_string();
+                let expected = collator.get_key(&input).to_bytes();
+
+                for part_len in [1, 3, 8] {
+                    let mut parts = collator.sort_key_parts(&input);
+                    let mut buffer = vec![0; part_len];
+                    let mut result = vec![];
+
+                    loop {
+                        let written = parts.next_part(&mut buffer);
+                        result.extend_from_slice(&buffer[.. written]);
+
+                        if written < part_len {
+                            break;
+                        }
+                    }
+
+                    assert!(parts.is_finished());
+                    assert_eq!(result, expected, "{}", test.description);
+                }
+            }
+        }
+    }
+}
+
+/// первая часть ключа содержит только первичные веса - остальные уровни не вычисляются
+#[test]
+fn test_sort_key_first_part()
+{
+    let collator = Collator::new(CollatorOptions::default());
+
+    let mut parts = collator.sort_key_parts("Müller");
+    let mut buffer = [0; 4];
+
+    assert_eq!(parts.next_part(&mut buffer), 4);
+    assert_eq!(buffer[..], collator.get_key("mu").to_bytes()[.. 4]);
+    assert!(!parts.is_finished());
+}
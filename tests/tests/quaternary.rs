@@ -0,0 +1,112 @@
+use unicode_collator::{
+    options::{AlternateHandling, CollatorOptions, QuaternaryMode, Strength},
+    Collator,
+};
+use unicode_data::COLLATION_TEST_CLDR_SHIFTED;
+
+/// четвертичный уровень ключа
+fn quaternary(mode: QuaternaryMode, input: &str) -> Vec<u16>
+{
+    let collator = Collator::new(CollatorOptions {
+        strength: Strength::Quaternary,
+        alternate: AlternateHandling::Shifted,
+        quaternary_mode: mode,
+        ..Default::default()
+    });
+
+    collator.get_key(input).level(3).to_vec()
+}
+
+/// расхождения TR10 и тестов CLDR (ISSUES_SHIFTED.md)
+#[test]
+fn test_quaternary_mode()
+{
+    // вычисляемые веса: второй элемент не имеет третичного веса
+    assert_eq!(
+        quaternary(QuaternaryMode::Cldr, "\u{FFF01}?"),
+        [0xFFFF, 0x016E]
+    );
+    assert_eq!(
+        quaternary(QuaternaryMode::Uca, "\u{FFF01}?"),
+        [0xFFFF, 0xFFFF, 0x016E]
+    );
+    assert_eq!(quaternary(QuaternaryMode::Cldr, "中"), [0xFFFF]);
+    assert_eq!(quaternary(QuaternaryMode::Uca, "中"), [0xFFFF, 0xFFFF]);
+
+    // U+FFFE
+    assert_eq!(
+        quaternary(QuaternaryMode::Cldr, "\u{FFFE}!"),
+        [0x0001, 0x0167]
+    );
+    assert_eq!(
+        quaternary(QuaternaryMode::Uca, "\u{FFFE}!"),
+        [0xFFFF, 0x0167]
+    );
+
+    // в остальных случаях режимы совпадают
+    for input in ["a\u{300}", "a b", "\u{300}"] {
+        assert_eq!(
+            quaternary(QuaternaryMode::Cldr, input),
+            quaternary(QuaternaryMode::Uca, input)
+        );
+    }
+}
+
+/// QuaternaryMode::Uca на тестах CLDR (Shifted): L1 - L3 совпадают с тестами, L4 - с весами,
+/// вычисленными по правилам TR10
+#[test]
+fn test_quaternary_uca_conformance()
+{
+    let collator = Collator::new(CollatorOptions {
+        strength: Strength::Quaternary,
+        alternate: AlternateHandling::Shifted,
+        quaternary_mode: QuaternaryMode::Uca,
+        ..Default::default()
+    });
+
+    for test in COLLATION_TEST_CLDR_SHIFTED.iter() {
+        let input = test.as_string();
+        let key = collator.get_key(&input);
+
+        for (level, expected) in [&test.l1, &test.l2, &test.l3].into_iter().enumerate() {
+            assert_eq!(key.level(level), expected.as_slice(), "{:04X?}", test.codes);
+        }
+
+        assert_eq!(
+            key.level(3),
+            tr10_quaternary(&collator.get_weights(&input)).as_slice(),
+            "{:04X?}",
+            test.codes
+        );
+    }
+}
+
+/// четвертичный уровень по таблице TR10 (Variable Weighting, Shifted)
+fn tr10_quaternary(weights: &[u32]) -> Vec<u16>
+{
+    let mut following_a_variable = false;
+
+    weights
+        .iter()
+        .filter_map(|&weights| {
+            let l1 = weights as u16;
+            let l3 = (weights >> 25) & 0x1F;
+            let is_variable = (weights >> 30) != 0;
+
+            match (l1, is_variable) {
+                // полностью игнорируемый вес
+                _ if weights == 0 => None,
+                // L1 = 0: FFFF, если не следует за переменным весом
+                (0, _) => (l3 != 0 && !following_a_variable).then_some(0xFFFF),
+                (l1, true) => {
+                    following_a_variable = true;
+                    Some(l1)
+                }
+                (_, false) => {
+                    following_a_variable = false;
+                    Some(0xFFFF)
+                }
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use unicode_collation_builder::build;
+use unicode_collator::{
+    key::compare_keys,
+    options::{AlternateHandling, CollatorOptions, OptionsError, ReorderCode, Strength},
+    Collator, CollatorData,
+};
+
+use ReorderCode::*;
+
+const CYRL: ReorderCode = Script(*b"Cyrl");
+const GREK: ReorderCode = Script(*b"Grek");
+const LATN: ReorderCode = Script(*b"Latn");
+
+/// порядок строк при заданном порядке групп - при сравнении и по ключам
+fn assert_order(reorder: &[ReorderCode], alternate: AlternateHandling, expected: &[&str])
+{
+    let collator = Collator::new(CollatorOptions {
+        strength: Strength::Quaternary,
+        alternate,
+        reorder: reorder.to_vec(),
+        ..Default::default()
+    });
+
+    let mut strings = expected.to_vec();
+    strings.reverse();
+
+    strings.sort_by(|a, b| collator.compare(a, b));
+    assert_eq!(strings, expected, "{:?}", reorder);
+
+    strings.reverse();
+
+    strings
+        .sort_by(|a, b| compare_keys(&collator.get_key(a).weights, &collator.get_key(b).weights));
+    assert_eq!(strings, expected, "{:?}", reorder);
+}
+
+/// примеры из UTS #35 (kr): перестановка письменностей и специальных групп
+#[test]
+fn test_reorder()
+{
+    for (reorder, expected) in [
+        (vec![], ["-", "$", "1", "a", "α", "я"]),
+        (vec![CYRL], ["-", "$", "1", "я", "a", "α"]),
+        (vec![GREK], ["-", "$", "1", "α", "a", "я"]),
+        (vec![LATN, Digit], ["-", "$", "a", "1", "α", "я"]),
+        (vec![Others, Digit], ["-", "$", "a", "α", "я", "1"]),
+        (vec![Others, GREK], ["-", "$", "1", "a", "я", "α"]),
+        (vec![GREK, Others, Digit], ["-", "$", "α", "a", "я", "1"]),
+        (vec![Digit, Currency, Punct], ["1", "$", "-", "a", "α", "я"]),
+        (
+            vec![CYRL, LATN, Digit, Currency],
+            ["-", "я", "a", "1", "$", "α"],
+        ),
+    ] {
+        assert_order(&reorder, AlternateHandling::NonIgnorable, &expected);
+    }
+}
+
+/// перестановка не затрагивает порядок внутри группы и вычисляемые веса
+#[test]
+fn test_reorder_implicit()
+{
+    assert_order(
+        &[Script(*b"Hani"), Script(*b"Hira"), CYRL],
+        AlternateHandling::NonIgnorable,
+        &[
+            "一",
+            "丁",
+            "中",
+            "あ",
+            "ア",
+            "ж",
+            "я",
+            "a",
+            "\u{E000}",
+            "\u{10FFFF}",
+        ],
+    );
+}
+
+/// Shifted: переменные веса определяются по исходным группам, на четвертичном уровне
+/// сравниваются переставленные веса
+#[test]
+fn test_reorder_shifted()
+{
+    assert_order(
+        &[LATN, Punct, Space],
+        AlternateHandling::Shifted,
+        &["a+b", "a1b", "a-b", "a b", "ab"],
+    );
+}
+
+/// коды групп из строк
+#[test]
+fn test_reorder_code()
+{
+    assert_eq!(ReorderCode::from_code("cyrl"), Some(CYRL));
+    assert_eq!(ReorderCode::from_code("LATN"), Some(LATN));
+    assert_eq!(ReorderCode::from_code("Zzzz"), Some(Others));
+    assert_eq!(ReorderCode::from_code("digit"), Some(Digit));
+    assert_eq!(ReorderCode::from_code("hira"), Some(Script(*b"Hira")));
+    assert_eq!(ReorderCode::from_code("Abcd"), None);
+    assert_eq!(ReorderCode::from_code("latin"), None);
+}
+
+/// reorder для таблиц без границ групп первичных весов не поддерживается
+#[test]
+fn test_reorder_without_groups()
+{
+    let unicode_data = "0061;LATIN SMALL LETTER A;Ll;0;L;;;;;N;;;;0041;\n";
+    let allkeys = "@version 15.1.0\n\n0061 ; [.2000.0020.0002] # LATIN SMALL LETTER A\n";
+
+    let tables = build(allkeys, unicode_data).unwrap();
+    let data = Arc::new(CollatorData::try_from_baked(tables.as_weights_data()).unwrap());
+
+    assert!(Collator::from_data(data.clone(), CollatorOptions::default()).is_ok());
+
+    for reorder in [vec![GREK], vec![Digit, Others], vec![Script(*b"Zzzz")]] {
+        let options = CollatorOptions {
+            reorder,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Collator::from_data(data.clone(), options).err(),
+            Some(OptionsError::Reorder)
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use unicode_collator::{
+    key::Key,
+    options::{AlternateHandling, CollatorOptions, CollatorOptionsValue, ReorderCode, Strength},
+    weights::Weights,
+    Collator,
+};
+
+/// опции в JSON - читаемые значения, неуказанные опции - по умолчанию
+#[test]
+fn test_serde_options()
+{
+    let options = CollatorOptions {
+        strength: Strength::Tetriary,
+        alternate: AlternateHandling::Shifted,
+        reorder: vec![ReorderCode::Script(*b"Cyrl"), ReorderCode::Digit],
+        ..Default::default()
+    };
+
+    let json = serde_json::to_string(&options).unwrap();
+
+    assert!(json.contains("\"strength\":\"tertiary\""));
+    assert!(json.contains("\"alternate\":\"shifted\""));
+    assert!(json.contains("\"reorder\":[\"Cyrl\",\"digit\"]"));
+
+    let restored: CollatorOptions = serde_json::from_str(&json).unwrap();
+    assert_eq!(format!("{:?}", restored), format!("{:?}", options));
+
+    let options: CollatorOptions =
+        serde_json::from_str(r#"{"strength": "primary", "alternate": "shift-trimmed"}"#).unwrap();
+    assert_eq!(options.strength, Strength::Primary);
+    assert_eq!(options.alternate, AlternateHandling::ShiftTrimmed);
+    assert!(options.normalization);
+
+    assert!(serde_json::from_str::<CollatorOptions>(r#"{"strength": "level2"}"#).is_err());
+    assert!(serde_json::from_str::<CollatorOptions>(r#"{"reorder": ["Abcd"]}"#).is_err());
+}
+
+/// сохранённое значение опций
+#[test]
+fn test_serde_options_value()
+{
+    let value = CollatorOptionsValue::from(CollatorOptions {
+        strength: Strength::Quaternary,
+        ..Default::default()
+    });
+
+    let json = serde_json::to_string(&value).unwrap();
+    let restored: CollatorOptionsValue = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored, value);
+    assert_eq!(
+        CollatorOptions::try_from(restored).unwrap().strength,
+        Strength::Quaternary
+    );
+}
+
+/// ключи и веса
+#[test]
+fn test_serde_key()
+{
+    let collator = Collator::new(CollatorOptions {
+        case_level: true,
+        ..Default::default()
+    });
+
+    let key = collator.get_key("Ключ");
+    let restored: Key = serde_json::from_str(&serde_json::to_string(&key).unwrap()).unwrap();
+
+    assert_eq!(restored.weights, key.weights);
+    assert_eq!(restored.to_bytes(), key.to_bytes());
+
+    let weights = collator.get_weights("a");
+    let json = serde_json::to_string(&Weights::from_slice(&weights)).unwrap();
+    let restored: Vec<Weights> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(json, format!("{:?}", weights).replace(' ', ""));
+    assert_eq!(restored[0].value(), weights[0]);
+}
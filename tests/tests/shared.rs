@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use unicode_collator::{
+    options::{AlternateHandling, CollatorOptions, ReorderCode, Strength},
+    Collator, CollatorData,
+};
+
+const STRINGS: [&str; 10] = [
+    "abc",
+    "ABC",
+    "a-b c",
+    "a\u{301}\u{316}",
+    "file10.txt",
+    "file9.txt",
+    "Ёлка",
+    "ελλάδα",
+    "한국어",
+    "",
+];
+
+/// опции, отличные от опций по умолчанию
+fn options_list() -> Vec<CollatorOptions>
+{
+    vec![
+        CollatorOptions::default(),
+        CollatorOptions {
+            strength: Strength::Identical,
+            alternate: AlternateHandling::Shifted,
+            ..Default::default()
+        },
+        CollatorOptions {
+            numeric: true,
+            normalization: false,
+            ..Default::default()
+        },
+        CollatorOptions {
+            reorder: vec![ReorderCode::Digit, ReorderCode::Script(*b"Cyrl")],
+            backwards_secondary: true,
+            ..Default::default()
+        },
+    ]
+}
+
+/// коллатор with_options сравнивает так же, как коллатор, созданный с теми же опциями
+#[test]
+fn test_with_options()
+{
+    let base = Collator::new(CollatorOptions::default());
+
+    for options in options_list() {
+        let collator = Collator::new(options.clone());
+        let view = base.with_options(options).unwrap();
+
+        for a in STRINGS {
+            assert_eq!(
+                view.get_key(a).weights,
+                collator.get_key(a).weights,
+                "{:?}",
+                a
+            );
+
+            for b in STRINGS {
+                assert_eq!(
+                    view.compare(a, b),
+                    collator.compare(a, b),
+                    "{:?} {:?}",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+}
+
+/// get_key_with - ключ с другими опциями без создания коллатора
+#[test]
+fn test_get_key_with()
+{
+    let base = Collator::new(CollatorOptions::default());
+
+    for options in options_list() {
+        let collator = Collator::new(options.clone());
+
+        for input in STRINGS {
+            assert_eq!(
+                base.get_key_with(input, &options).unwrap().weights,
+                collator.get_key(input).weights,
+                "{:?}",
+                input
+            );
+        }
+    }
+}
+
+/// таблицы весов общие для коллаторов, созданных из одних данных
+#[test]
+fn test_shared_data()
+{
+    let data = Arc::new(CollatorData::new());
+
+    let a = Collator::from_data(data.clone(), CollatorOptions::default()).unwrap();
+    let b = Collator::from_data(
+        data.clone(),
+        CollatorOptions {
+            strength: Strength::Primary,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(Arc::strong_count(&data), 3);
+    assert_eq!(a.compare("a", "A"), core::cmp::Ordering::Less);
+    assert_eq!(b.compare("a", "A"), core::cmp::Ordering::Equal);
+}
+
+/// таблицы весов, подготовленные при компиляции
+static DATA: CollatorData<'static> = CollatorData::new();
+
+/// коллатор над статическими таблицами сравнивает так же, как Collator::new
+#[test]
+fn test_static_data()
+{
+    let base = Collator::new(CollatorOptions::default());
+
+    for options in options_list() {
+        let collator = Collator::from_static(&DATA, options.clone()).unwrap();
+        let view = base.with_options(options).unwrap();
+
+        for input in STRINGS {
+            assert_eq!(
+                collator.get_key(input).weights,
+                view.get_key(input).weights,
+                "{:?}",
+                input
+            );
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use unicode_collator::{
+    cldr_und,
+    container::{self, ContainerError, DataVersion},
+    options::CollatorOptions,
+    Collator, DataError, WeightsData,
+};
+
+/// изменяемая копия таблиц
+struct Tables
+{
+    index: Vec<u16>,
+    scalars32: Vec<u32>,
+    scalars64: Vec<u64>,
+    expansions: Vec<u32>,
+    tries: Vec<u32>,
+    continuous_block_end: u32,
+}
+
+impl Tables
+{
+    fn new() -> Self
+    {
+        let data = cldr_und();
+
+        Self {
+            index: data.index.to_vec(),
+            scalars32: data.scalars32.to_vec(),
+            scalars64: data.scalars64.to_vec(),
+            expansions: data.expansions.to_vec(),
+            tries: data.tries.to_vec(),
+            continuous_block_end: data.continuous_block_end,
+        }
+    }
+
+    fn data(&self) -> WeightsData<'_>
+    {
+        WeightsData {
+            index: &self.index,
+            scalars32: &self.scalars32,
+            scalars64: &self.scalars64,
+            expansions: &self.expansions,
+            tries: &self.tries,
+            continuous_block_end: self.continuous_block_end,
+        }
+    }
+
+    /// позиция в tries первой записи о кодпоинте с маркером marker (кроме слогов хангыль)
+    fn trie_pos(&self, marker: u64) -> usize
+    {
+        self.scalars32
+            .iter()
+            .map(|&value| value as u64)
+            .chain(self.scalars64.iter().copied())
+            .find(|&value| value & 7 == marker && (value >> 20) as u8 != 0xFF)
+            .map(|value| (value >> 4) as u16 as usize)
+            .unwrap()
+    }
+
+    fn validate(&self) -> Result<(), DataError>
+    {
+        Collator::try_from_baked(self.data(), CollatorOptions::default()).map(|_| ())
+    }
+}
+
+/// встроенные таблицы проходят проверку
+#[test]
+fn test_valid_data()
+{
+    let tables = Tables::new();
+
+    assert_eq!(tables.validate(), Ok(()));
+
+    let collator = Collator::try_from_baked(tables.data(), CollatorOptions::default()).unwrap();
+    let baked = Collator::new(CollatorOptions::default());
+
+    for input in [
+        "abc",
+        "Ёлка",
+        "l·l",
+        "한국어",
+        "\u{FDFA}",
+        "a\u{301}\u{316}",
+    ] {
+        assert_eq!(
+            collator.get_key(input).weights,
+            baked.get_key(input).weights
+        );
+    }
+}
+
+/// повреждённые таблицы отклоняются
+#[test]
+fn test_invalid_data()
+{
+    let mut tables = Tables::new();
+    tables.continuous_block_end = 0x1FFF;
+    assert_eq!(
+        tables.validate(),
+        Err(DataError::InvalidContinuousBlockEnd(0x1FFF))
+    );
+
+    let mut tables = Tables::new();
+    tables.index.truncate(0x600);
+    assert_eq!(tables.validate(), Err(DataError::IndexOutOfBounds(0)));
+
+    let mut tables = Tables::new();
+    tables.scalars32.truncate(16);
+    assert!(matches!(
+        tables.validate(),
+        Err(DataError::IndexOutOfBounds(_))
+    ));
+
+    // неизвестный маркер у первого кодпоинта с одинарными весами в scalars32
+    let mut tables = Tables::new();
+    let pos = tables
+        .scalars32
+        .iter()
+        .position(|&value| value & 7 == 1)
+        .unwrap();
+    tables.scalars32[pos] |= 7;
+    assert!(matches!(
+        tables.validate(),
+        Err(DataError::InvalidMarker { marker: 7, .. })
+    ));
+
+    let mut tables = Tables::new();
+    tables.expansions.truncate(tables.expansions.len() / 2);
+    assert!(matches!(
+        tables.validate(),
+        Err(DataError::ExpansionOutOfBounds(_))
+    ));
+
+    let mut tables = Tables::new();
+    tables.tries.truncate(tables.tries.len() / 2);
+    assert!(matches!(
+        tables.validate(),
+        Err(DataError::InvalidTrie(_) | DataError::InvalidDecomposition(_))
+    ));
+
+    // узлы бора без признака последнего элемента - списки не заканчиваются
+    let mut tables = Tables::new();
+    tables.tries.iter_mut().for_each(|value| *value &= !2);
+    assert!(matches!(tables.validate(), Err(DataError::InvalidTrie(_))));
+}
+
+/// записи, которые привели бы к недопустимому состоянию коллатора
+#[test]
+fn test_invalid_entries()
+{
+    // нестартер с одинарными весами и нулевым CCC
+    let mut tables = Tables::new();
+    let pos = tables
+        .scalars64
+        .iter()
+        .position(|&value| value & 7 == 5)
+        .unwrap();
+    tables.scalars64[pos] &= !(0xFF << 36);
+    assert!(matches!(
+        tables.validate(),
+        Err(DataError::InvalidNonstarter(_))
+    ));
+
+    // узел последовательности нестартера с нулевым CCC
+    let mut tables = Tables::new();
+    let pos = tables.trie_pos(6);
+    tables.tries[pos] &= !(0x3F << 2);
+    assert!(matches!(
+        tables.validate(),
+        Err(DataError::InvalidNonstarter(_))
+    ));
+
+    // декомпозиция начинается с нестартера
+    let mut tables = Tables::new();
+    let pos = tables.trie_pos(3);
+    let first = pos + 1 + (tables.tries[pos] >> 26) as usize;
+    tables.tries[first] |= 1 << 2;
+    assert!(matches!(
+        tables.validate(),
+        Err(DataError::InvalidDecomposition(_))
+    ));
+}
+
+/// расширение в конце адресуемой части expansions: конец вычисляется в u16
+#[test]
+fn test_expansion_at_boundary()
+{
+    let baked = Collator::new(CollatorOptions::default());
+
+    let tables = Tables::new();
+    let entry = tables
+        .scalars32
+        .iter()
+        .position(|&value| value & 7 == 2)
+        .unwrap();
+    let value = tables.scalars32[entry];
+    let (pos, len) = ((value >> 4) as u16 as usize, (value >> 20) as u8 as usize);
+
+    for (end, expected) in [
+        (u16::MAX as usize, Ok(())),
+        (u16::MAX as usize + 1, Err(())),
+    ] {
+        let mut tables = Tables::new();
+        let start = end - len;
+        let weights = tables.expansions[pos .. pos + len].to_vec();
+
+        tables.expansions.resize(start, 0);
+        tables.expansions.extend_from_slice(&weights);
+        tables.scalars32[entry] = value & !(0xFFFF << 4) | ((start as u32) & 0xFFFF) << 4;
+
+        let result = tables.validate();
+
+        match expected {
+            Ok(()) => {
+                assert_eq!(result, Ok(()));
+
+                let collator =
+                    Collator::try_from_baked(tables.data(), CollatorOptions::default()).unwrap();
+
+                for c in (0 .. 0x10000).filter_map(char::from_u32) {
+                    let input = c.to_string();
+
+                    assert_eq!(collator.get_weights(&input), baked.get_weights(&input));
+                }
+            }
+            Err(()) => assert!(matches!(result, Err(DataError::ExpansionOutOfBounds(_)))),
+        }
+    }
+}
+
+/// пустые таблицы индекса и записей о кодпоинтах
+#[test]
+fn test_empty_tables()
+{
+    let mut tables = Tables::new();
+    tables.index.clear();
+    assert_eq!(tables.validate(), Err(DataError::EmptyTable("index")));
+
+    let mut tables = Tables::new();
+    tables.scalars32.clear();
+    assert_eq!(tables.validate(), Err(DataError::EmptyTable("scalars32")));
+
+    let mut tables = Tables::new();
+    tables.scalars64.clear();
+    assert_eq!(tables.validate(), Err(DataError::EmptyTable("scalars64")));
+}
+
+/// таблицы из контейнера проверяются
+#[test]
+fn test_invalid_container_data()
+{
+    let mut tables = Tables::new();
+    tables.expansions.truncate(tables.expansions.len() / 2);
+
+    let bytes = container::to_bytes(&tables.data(), DataVersion::default());
+    let buffer: Vec<u64> = bytes
+        .chunks(8)
+        .map(|chunk| u64::from_ne_bytes(chunk.try_into().unwrap()))
+        .collect();
+    let bytes = unsafe { core::slice::from_raw_parts(buffer.as_ptr() as *const u8, bytes.len()) };
+
+    assert!(container::read(bytes).is_ok());
+    assert!(matches!(
+        Collator::from_bytes(bytes, CollatorOptions::default()),
+        Err(ContainerError::InvalidData(
+            DataError::ExpansionOutOfBounds(_)
+        ))
+    ));
+}
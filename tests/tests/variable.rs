@@ -0,0 +1,95 @@
+use core::cmp::Ordering;
+
+use unicode_collation_builder::build;
+use unicode_collator::{
+    options::{AlternateHandling, CollatorOptions, MaxVariable, OptionsError},
+    Collator, DataError,
+};
+
+/// maxVariable: группы символов, игнорируемые в режиме Shifted
+#[test]
+fn test_max_variable()
+{
+    let strings = ["a b", "a-b", "a+b", "a$b", "a5b"];
+
+    for (max_variable, ignored) in [
+        (MaxVariable::Space, 1),
+        (MaxVariable::Punct, 2),
+        (MaxVariable::Symbol, 3),
+        (MaxVariable::Currency, 4),
+    ] {
+        let collator = Collator::new(CollatorOptions {
+            alternate: AlternateHandling::Shifted,
+            max_variable,
+            ..Default::default()
+        });
+
+        for (i, string) in strings.iter().enumerate() {
+            let expected = match i < ignored {
+                true => Ordering::Equal,
+                false => Ordering::Less,
+            };
+
+            assert_eq!(
+                collator.compare(string, "ab"),
+                expected,
+                "{:?} {}",
+                max_variable,
+                string
+            );
+            assert_eq!(
+                collator.get_key(string).weights == collator.get_key("ab").weights,
+                expected == Ordering::Equal
+            );
+        }
+    }
+}
+
+/// таблицы без границ групп: переменные веса - по флагу, maxVariable ≠ punct не поддерживается
+#[test]
+fn test_max_variable_without_groups()
+{
+    let unicode_data = "\
+0020;SPACE;Zs;0;WS;;;;;N;;;;;
+0024;DOLLAR SIGN;Sc;0;ET;;;;;N;;;;;
+0061;LATIN SMALL LETTER A;Ll;0;L;;;;;N;;;;0041;
+";
+
+    let allkeys = "\
+@version 15.1.0
+
+0020 ; [*0209.0020.0002] # SPACE
+0024 ; [.1000.0020.0002] # DOLLAR SIGN
+0061 ; [.2000.0020.0002] # LATIN SMALL LETTER A
+";
+
+    let tables = build(allkeys, unicode_data).unwrap();
+
+    let options = CollatorOptions {
+        alternate: AlternateHandling::Shifted,
+        ..Default::default()
+    };
+
+    let collator = Collator::try_from_baked(tables.as_weights_data(), options.clone()).unwrap();
+
+    assert_eq!(collator.compare("a a", "aa"), Ordering::Equal);
+    assert_eq!(collator.compare("a$a", "aa"), Ordering::Less);
+
+    for max_variable in [
+        MaxVariable::Space,
+        MaxVariable::Symbol,
+        MaxVariable::Currency,
+    ] {
+        let options = CollatorOptions {
+            max_variable,
+            ..options.clone()
+        };
+
+        assert_eq!(
+            Collator::try_from_baked(tables.as_weights_data(), options).err(),
+            Some(DataError::UnsupportedOptions(OptionsError::MaxVariable(
+                max_variable
+            )))
+        );
+    }
+}